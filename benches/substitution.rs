@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use albhed_translator_service::domain::entities::{OriginalText, SubstitutionTable};
+use albhed_translator_service::domain::parallel::{self, DEFAULT_CHUNK_SIZE};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use unicode_segmentation::UnicodeSegmentation;
+
+const SIZE: usize = 4 * 1024 * 1024;
+
+fn repeat(sample: &str) -> String {
+    sample.repeat(SIZE / sample.len() + 1)
+}
+
+//以前の実装と同じく、クラスタごとに HashMap を引いて String を作り、さらにコピーする
+fn hashmap_encode(mapping: &HashMap<char, char>, text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for grapheme in text.graphemes(true) {
+        let mut chars = grapheme.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => encoded.push(mapping.get(&c).copied().unwrap_or(c)),
+            _ => encoded.push_str(grapheme),
+        }
+    }
+    encoded.as_str().to_string()
+}
+
+fn substitution(c: &mut Criterion) {
+    let table = SubstitutionTable::albhed();
+    let mapping: HashMap<char, char> = table.pairs().into_iter().collect();
+    let inputs = [
+        (
+            "ascii",
+            repeat("HELLO, WORLD! THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG. "),
+        ),
+        ("kana", repeat("じかんがねえ！ さっさと はっしんしろ！ ")),
+        ("unchanged", repeat("0123456789 +-*/ () [] {} ... ")),
+    ];
+
+    let mut group = c.benchmark_group("encode");
+    for (name, input) in &inputs {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("hashmap", name), input, |b, input| {
+            b.iter(|| hashmap_encode(&mapping, black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("lookup", name), input, |b, input| {
+            b.iter(|| table.encode(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", name), input, |b, input| {
+            b.iter(|| parallel::encode(table, black_box(input), DEFAULT_CHUNK_SIZE / 4))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("original_text", name),
+            input,
+            |b, input| {
+                b.iter(|| {
+                    OriginalText::new(black_box(input.as_str()))
+                        .unwrap()
+                        .encode()
+                        .into_text()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, substitution);
+criterion_main!(benches);
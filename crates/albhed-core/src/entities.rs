@@ -0,0 +1,999 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+use once_cell::race::OnceBox;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::DomainError;
+
+pub struct AlBhedText<'a> {
+    text: Cow<'a, str>,
+}
+
+impl<'a> AlBhedText<'a> {
+    //&str はコピーせずに借り、String はそのまま受け取る
+    pub fn new(input: impl Into<Cow<'a, str>>) -> Result<Self, DomainError> {
+        let text = input.into();
+        if text.is_empty() {
+            return Err(DomainError::EmptyText);
+        }
+
+        Ok(AlBhedText { text })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_text(self) -> Cow<'a, str> {
+        self.text
+    }
+
+    pub fn decode(&self) -> OriginalText<'a> {
+        self.decode_with(SubstitutionTable::albhed())
+    }
+
+    pub fn decode_with(&self, table: &SubstitutionTable) -> OriginalText<'a> {
+        self.decode_except(table, &[])
+    }
+
+    //指定した範囲（文字位置）はそのまま残して変換する
+    pub fn decode_except(
+        &self,
+        table: &SubstitutionTable,
+        excluded: &[Range<usize>],
+    ) -> OriginalText<'a> {
+        OriginalText {
+            text: rewrite(&self.text, excluded, |c| table.decoder.get(c)),
+        }
+    }
+}
+
+pub struct OriginalText<'a> {
+    text: Cow<'a, str>,
+}
+
+impl<'a> OriginalText<'a> {
+    //&str はコピーせずに借り、String はそのまま受け取る
+    pub fn new(input: impl Into<Cow<'a, str>>) -> Result<Self, DomainError> {
+        let text = input.into();
+        if text.is_empty() {
+            return Err(DomainError::EmptyText);
+        }
+
+        Ok(OriginalText { text })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_text(self) -> Cow<'a, str> {
+        self.text
+    }
+
+    pub fn encode(&self) -> AlBhedText<'a> {
+        self.encode_with(SubstitutionTable::albhed())
+    }
+
+    pub fn encode_with(&self, table: &SubstitutionTable) -> AlBhedText<'a> {
+        self.encode_except(table, &[])
+    }
+
+    //指定した範囲（文字位置）はそのまま残して変換する
+    pub fn encode_except(
+        &self,
+        table: &SubstitutionTable,
+        excluded: &[Range<usize>],
+    ) -> AlBhedText<'a> {
+        AlBhedText {
+            text: rewrite(&self.text, excluded, |c| table.encoder.get(c)),
+        }
+    }
+}
+
+impl fmt::Display for AlBhedText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl AsRef<str> for AlBhedText<'_> {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl FromStr for AlBhedText<'static> {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AlBhedText::new(String::from(s))
+    }
+}
+
+impl TryFrom<String> for AlBhedText<'static> {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        AlBhedText::new(value)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for AlBhedText<'a> {
+    type Error = DomainError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        AlBhedText::new(value)
+    }
+}
+
+impl fmt::Display for OriginalText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl AsRef<str> for OriginalText<'_> {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl FromStr for OriginalText<'static> {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OriginalText::new(String::from(s))
+    }
+}
+
+impl TryFrom<String> for OriginalText<'static> {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        OriginalText::new(value)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for OriginalText<'a> {
+    type Error = DomainError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        OriginalText::new(value)
+    }
+}
+
+//"hi".to_albhed() のように組み込みの表で直接変換する
+pub trait AlBhedExt {
+    fn to_albhed(&self) -> Cow<'_, str>;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_albhed(&self) -> Cow<'_, str>;
+}
+
+impl AlBhedExt for str {
+    fn to_albhed(&self) -> Cow<'_, str> {
+        SubstitutionTable::albhed().encode(self)
+    }
+
+    fn from_albhed(&self) -> Cow<'_, str> {
+        SubstitutionTable::albhed().decode(self)
+    }
+}
+
+//借りたテキストが何も変わらなければ、結果も同じテキストを借りたまま返す
+fn rewrite<'a>(
+    text: &Cow<'a, str>,
+    excluded: &[Range<usize>],
+    convert: impl Fn(char) -> char,
+) -> Cow<'a, str> {
+    match text {
+        Cow::Borrowed(text) => translate_except(text, excluded, convert),
+        Cow::Owned(text) => Cow::Owned(translate_except(text, excluded, convert).into_owned()),
+    }
+}
+
+//変わらなかった部分は書き出さず、最初に変わったところで初めて String を確保する
+struct Rewriter<'a> {
+    source: &'a str,
+    output: Option<String>,
+}
+
+impl<'a> Rewriter<'a> {
+    fn new(source: &'a str) -> Self {
+        Rewriter {
+            source,
+            output: None,
+        }
+    }
+
+    //offset（バイト位置）までの元のテキストを書き出した出力
+    fn start(&mut self, offset: usize) -> &mut String {
+        let source = self.source;
+        self.output.get_or_insert_with(|| {
+            let mut s = String::with_capacity(source.len());
+            s.push_str(&source[..offset]);
+            s
+        })
+    }
+
+    fn push(&mut self, offset: usize, original: char, converted: char) {
+        if converted != original || self.output.is_some() {
+            self.start(offset).push(converted);
+        }
+    }
+
+    fn keep(&mut self, piece: &str) {
+        if let Some(s) = &mut self.output {
+            s.push_str(piece);
+        }
+    }
+
+    fn finish(self) -> Cow<'a, str> {
+        match self.output {
+            Some(s) => Cow::Owned(s),
+            None => Cow::Borrowed(self.source),
+        }
+    }
+}
+
+//書記素クラスタ単位で変換する
+//文字＋結合ダイアクリティカルマーク、かな＋濁点・半濁点以外のクラスタはそのまま残す
+fn translate(text: &str, convert: impl Fn(char) -> char) -> Cow<'_, str> {
+    let mut out = Rewriter::new(text);
+
+    //ASCIIだけならバイト単位で見る。複数の文字からなるクラスタは CRLF しかない
+    if text.is_ascii() {
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+                out.keep("\r\n");
+                i += 2;
+                continue;
+            }
+            let c = bytes[i] as char;
+            out.push(i, c, convert(c));
+            i += 1;
+        }
+        return out.finish();
+    }
+
+    //単独でクラスタになる文字だけなら、クラスタに分割せずに文字単位で見る
+    if text.chars().all(is_standalone) {
+        let mut chars = text.char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            if c == '\r' && chars.next_if(|&(_, next)| next == '\n').is_some() {
+                out.keep("\r\n");
+                continue;
+            }
+            out.push(offset, c, convert(c));
+        }
+        return out.finish();
+    }
+
+    for (offset, grapheme) in text.grapheme_indices(true) {
+        let mut chars = grapheme.chars();
+        let base = chars.next().unwrap();
+        let marks = chars.as_str();
+
+        if marks.is_empty() {
+            out.push(offset, base, convert(base));
+        } else if base.is_ascii_alphabetic() && marks.chars().all(is_combining_diacritic) {
+            out.push(offset, base, convert(base));
+            out.keep(marks);
+        } else if let Some(voiced) = compose_voicing(base, marks) {
+            //変換しない文字は合成せず、元のまま残す
+            match convert(voiced) {
+                converted if converted == voiced => out.keep(grapheme),
+                converted => out.start(offset).push(converted),
+            }
+        } else {
+            out.keep(grapheme);
+        }
+    }
+    out.finish()
+}
+
+fn translate_except<'a>(
+    text: &'a str,
+    excluded: &[Range<usize>],
+    convert: impl Fn(char) -> char,
+) -> Cow<'a, str> {
+    if excluded.is_empty() {
+        return translate(text, convert);
+    }
+
+    let offsets: Vec<usize> = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([text.len()])
+        .collect();
+    let byte_offset = |index: usize| offsets[index.min(offsets.len() - 1)];
+
+    let mut ranges = excluded.to_vec();
+    ranges.sort_by_key(|range| range.start);
+
+    let mut s = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in ranges {
+        //逆向きの範囲や、前の範囲に含まれる範囲は飛ばす
+        let (start, end) = (byte_offset(range.start).max(last), byte_offset(range.end));
+        if end <= start {
+            continue;
+        }
+        s.push_str(&translate(&text[last..start], &convert));
+        s.push_str(&text[start..end]);
+        last = end;
+    }
+    s.push_str(&translate(&text[last..], &convert));
+    Cow::Owned(s)
+}
+
+//前後の文字と同じ書記素クラスタにならない文字（CRLF を除く）
+//結合文字（U+302A〜U+302F、U+3099、U+309A、U+FF9E、U+FF9F）と絵文字（U+3030、U+303D）は含めない
+fn is_standalone(c: char) -> bool {
+    matches!(c,
+        '\0'..='\u{7F}'
+        | '\u{3000}'..='\u{3029}'
+        | '\u{3031}'..='\u{303C}'
+        | '\u{303E}'..='\u{3098}'
+        | '\u{309B}'..='\u{30FF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{FF01}'..='\u{FF9D}')
+}
+
+fn is_combining_diacritic(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
+}
+
+const DAKUTEN_BASES: &str =
+    "かきくけこさしすせそたちつてとはひふへほカキクケコサシスセソタチツテトハヒフヘホ";
+const HANDAKUTEN_BASES: &str = "はひふへほハヒフヘホ";
+
+//かな＋結合用濁点・半濁点を合成済みの文字にする
+fn compose_voicing(base: char, marks: &str) -> Option<char> {
+    let offset = match marks {
+        "\u{3099}" if DAKUTEN_BASES.contains(base) => 1,
+        "\u{309A}" if HANDAKUTEN_BASES.contains(base) => 2,
+        _ => return None,
+    };
+    char::from_u32(base as u32 + offset)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Script {
+    Latin,
+    Kana,
+}
+
+impl Script {
+    //この文字を変換する言語の変換ルール
+    pub fn language(&self) -> Language {
+        match self {
+            Script::Latin => Language::English,
+            Script::Kana => Language::Japanese,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    Japanese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Japanese];
+
+    //文字がどの言語の変換ルールに属するか
+    pub fn of(c: char) -> Option<Language> {
+        match c {
+            'A'..='Z' | 'a'..='z' => Some(Language::English),
+            'ぁ'..='ゖ' | 'ァ'..='ヺ' => Some(Language::Japanese),
+            _ => None,
+        }
+    }
+
+    pub fn script(&self) -> Script {
+        match self {
+            Language::English => Script::Latin,
+            Language::Japanese => Script::Kana,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct RuleGroup {
+    language: Language,
+    to_albhed: BTreeMap<char, char>,
+    from_albhed: BTreeMap<char, char>,
+}
+
+//文字ごとの変換先を引く表
+//ASCIIとかなのブロック（U+3000〜U+30FF）は配列で、それ以外は BTreeMap で引く
+#[derive(Clone)]
+struct CharMap {
+    ascii: [char; 128],
+    kana: Box<[char; 256]>,
+    other: BTreeMap<char, char>,
+}
+
+impl CharMap {
+    const KANA_BLOCK: u32 = 0x3000;
+
+    //先のルールに同じ文字があれば、そちらを優先する
+    fn new<'a>(rules: impl DoubleEndedIterator<Item = &'a BTreeMap<char, char>>) -> Self {
+        let mut map = CharMap {
+            ascii: core::array::from_fn(|i| i as u8 as char),
+            kana: Box::new(core::array::from_fn(|i| {
+                char::from_u32(Self::KANA_BLOCK + i as u32).unwrap()
+            })),
+            other: BTreeMap::new(),
+        };
+        for rule in rules.rev() {
+            for (&k, &v) in rule {
+                let code = k as u32;
+                if code < 128 {
+                    map.ascii[code as usize] = v;
+                } else if code & !0xFF == Self::KANA_BLOCK {
+                    map.kana[(code & 0xFF) as usize] = v;
+                } else {
+                    map.other.insert(k, v);
+                }
+            }
+        }
+        map
+    }
+
+    #[inline]
+    fn get(&self, c: char) -> char {
+        let code = c as u32;
+        if code < 128 {
+            self.ascii[code as usize]
+        } else if code & !0xFF == Self::KANA_BLOCK {
+            self.kana[(code & 0xFF) as usize]
+        } else {
+            self.other.get(&c).copied().unwrap_or(c)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SubstitutionTable {
+    groups: Vec<RuleGroup>,
+    encoder: CharMap,
+    decoder: CharMap,
+}
+
+impl SubstitutionTable {
+    pub fn new(mapping: impl IntoIterator<Item = (char, char)>) -> Result<Self, DomainError> {
+        let mut grouped: Vec<(Language, BTreeMap<char, char>)> = Vec::new();
+        for (k, v) in mapping {
+            let language = Language::of(k).ok_or(DomainError::UnsupportedCharacter(k))?;
+            match grouped.iter_mut().find(|(l, _)| *l == language) {
+                Some((_, rule)) => {
+                    rule.insert(k, v);
+                }
+                None => grouped.push((language, BTreeMap::from([(k, v)]))),
+            }
+        }
+        grouped.sort_by_key(|(language, _)| *language as usize);
+
+        Self::from_groups(grouped)
+    }
+
+    pub fn from_groups<R>(
+        groups: impl IntoIterator<Item = (Language, R)>,
+    ) -> Result<Self, DomainError>
+    where
+        R: IntoIterator<Item = (char, char)>,
+    {
+        let mut seen = BTreeMap::new();
+        let mut rule_groups = Vec::new();
+        for (language, rule) in groups {
+            let to_albhed: BTreeMap<char, char> = rule.into_iter().collect();
+            let mut from_albhed = BTreeMap::new();
+            for (&k, &v) in to_albhed.iter() {
+                if seen.insert(v, k).is_some() {
+                    return Err(DomainError::DuplicateMapping(v));
+                }
+                from_albhed.insert(v, k);
+            }
+            rule_groups.push(RuleGroup {
+                language,
+                to_albhed,
+                from_albhed,
+            });
+        }
+
+        Ok(Self::from_rule_groups(rule_groups))
+    }
+
+    fn from_rule_groups(groups: Vec<RuleGroup>) -> Self {
+        SubstitutionTable {
+            encoder: CharMap::new(groups.iter().map(|group| &group.to_albhed)),
+            decoder: CharMap::new(groups.iter().map(|group| &group.from_albhed)),
+            groups,
+        }
+    }
+
+    pub fn albhed() -> &'static SubstitutionTable {
+        ALBHED_TABLE.get_or_init(|| {
+            let rules: &[(Language, &[(char, char)])] = &[
+                #[cfg(feature = "english")]
+                (Language::English, ENGLISH_TO_ALBHED_RULE),
+                #[cfg(feature = "japanese")]
+                (Language::Japanese, JAPANESE_TO_ALBHED_RULE),
+            ];
+            let table = Self::from_groups(
+                rules
+                    .iter()
+                    .map(|&(language, rule)| (language, rule.iter().copied())),
+            );
+            Box::new(table.unwrap())
+        })
+    }
+
+    //指定した言語の変換ルールだけを残した表を返す
+    pub fn restricted_to(&self, languages: &[Language]) -> SubstitutionTable {
+        Self::from_rule_groups(
+            self.groups
+                .iter()
+                .filter(|group| languages.contains(&group.language))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    //覚えた文字（アルベド語辞書で解読済みの平文の文字）は変換せずに残した表を返す
+    pub fn without_plaintext(&self, letters: &[char]) -> SubstitutionTable {
+        Self::from_rule_groups(
+            self.groups
+                .iter()
+                .map(|group| RuleGroup {
+                    language: group.language,
+                    to_albhed: group
+                        .to_albhed
+                        .iter()
+                        .filter(|(k, _)| !letters.contains(k))
+                        .map(|(&k, &v)| (k, v))
+                        .collect(),
+                    from_albhed: group
+                        .from_albhed
+                        .iter()
+                        .filter(|(_, v)| !letters.contains(v))
+                        .map(|(&k, &v)| (k, v))
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+
+    //小文字も大文字と同じように変換し、小文字のまま返す表を返す
+    //すでに変換ルールのある文字や、変換先が重なる文字は追加しない
+    pub fn case_insensitive(&self) -> SubstitutionTable {
+        let mut groups = self.groups.clone();
+        for group in groups.iter_mut() {
+            let pairs: Vec<(char, char)> = group.to_albhed.iter().map(|(&k, &v)| (k, v)).collect();
+            for (k, v) in pairs {
+                let (lower_k, lower_v) = (k.to_ascii_lowercase(), v.to_ascii_lowercase());
+                if lower_k == k
+                    || self.encode_char(lower_k) != lower_k
+                    || self.decode_char(lower_v) != lower_v
+                    || group.from_albhed.contains_key(&lower_v)
+                {
+                    continue;
+                }
+                group.to_albhed.insert(lower_k, lower_v);
+                group.from_albhed.insert(lower_v, lower_k);
+            }
+        }
+        Self::from_rule_groups(groups)
+    }
+
+    pub fn languages(&self) -> Vec<Language> {
+        self.groups.iter().map(|group| group.language).collect()
+    }
+
+    pub fn encode_char(&self, c: char) -> char {
+        self.encoder.get(c)
+    }
+
+    pub fn decode_char(&self, c: char) -> char {
+        self.decoder.get(c)
+    }
+
+    //何も変わらなければ入力を借りたまま返す
+    pub fn encode<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        translate(text, |c| self.encoder.get(c))
+    }
+
+    pub fn decode<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        translate(text, |c| self.decoder.get(c))
+    }
+
+    //(平文, アルベド語) の組を平文の文字コード順で返す
+    pub fn pairs(&self) -> Vec<(char, char)> {
+        let mut pairs: Vec<(char, char)> = self
+            .groups
+            .iter()
+            .flat_map(|group| group.to_albhed.iter().map(|(&k, &v)| (k, v)))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    pub fn pairs_for(&self, language: Language) -> Vec<(char, char)> {
+        let mut pairs: Vec<(char, char)> = self
+            .groups
+            .iter()
+            .filter(|group| group.language == language)
+            .flat_map(|group| group.to_albhed.iter().map(|(&k, &v)| (k, v)))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+}
+
+#[cfg(feature = "english")]
+const ENGLISH_TO_ALBHED_RULE: &[(char, char)] = &[
+    //英語変換ルール
+    ('E', 'A'),
+    ('P', 'B'),
+    ('S', 'C'),
+    ('T', 'D'),
+    ('I', 'E'),
+    ('W', 'F'),
+    ('K', 'G'),
+    ('N', 'H'),
+    ('U', 'I'),
+    ('V', 'J'),
+    ('G', 'K'),
+    ('C', 'L'),
+    ('L', 'M'),
+    ('R', 'N'),
+    ('Y', 'O'),
+    ('B', 'P'),
+    ('X', 'Q'),
+    ('H', 'R'),
+    ('M', 'S'),
+    ('D', 'T'),
+    ('O', 'U'),
+    ('F', 'V'),
+    ('Z', 'W'),
+    ('Q', 'X'),
+    ('A', 'Y'),
+    ('J', 'Z'),
+];
+
+#[cfg(feature = "japanese")]
+const JAPANESE_TO_ALBHED_RULE: &[(char, char)] = &[
+    //日本語変換ルール
+    ('あ', 'ワ'),
+    ('い', 'ミ'),
+    ('う', 'フ'),
+    ('え', 'ネ'),
+    ('お', 'ト'),
+    ('か', 'ア'),
+    ('き', 'チ'),
+    ('く', 'ル'),
+    ('け', 'テ'),
+    ('こ', 'ヨ'),
+    ('さ', 'ラ'),
+    ('し', 'キ'),
+    ('す', 'ヌ'),
+    ('せ', 'へ'),
+    ('そ', 'ホ'),
+    ('た', 'サ'),
+    ('ち', 'ヒ'),
+    ('つ', 'ユ'),
+    ('て', 'セ'),
+    ('と', 'ソ'),
+    ('な', 'ハ'),
+    ('に', 'シ'),
+    ('ぬ', 'ス'),
+    ('ね', 'メ'),
+    ('の', 'オ'),
+    ('は', 'マ'),
+    ('ひ', 'リ'),
+    ('ふ', 'ク'),
+    ('へ', 'ケ'),
+    ('ほ', 'ロ'),
+    ('ま', 'ヤ'),
+    ('み', 'イ'),
+    ('む', 'ツ'),
+    ('め', 'レ'),
+    ('も', 'コ'),
+    ('や', 'タ'),
+    ('ゆ', 'ヲ'),
+    ('よ', 'モ'),
+    ('ら', 'ナ'),
+    ('り', 'ニ'),
+    ('る', 'ウ'),
+    ('れ', 'エ'),
+    ('ろ', 'ノ'),
+    ('わ', 'カ'),
+    ('を', 'ム'),
+    ('ん', 'ン'),
+    ('が', 'ダ'),
+    ('ぎ', 'ジ'),
+    ('ぐ', 'ヅ'),
+    ('げ', 'デ'),
+    ('ご', 'ゾ'),
+    ('ざ', 'バ'),
+    ('じ', 'ギ'),
+    ('ず', 'ブ'),
+    ('ぜ', 'ゲ'),
+    ('ぞ', 'ボ'),
+    ('だ', 'ガ'),
+    ('ぢ', 'ビ'),
+    ('づ', 'グ'),
+    ('で', 'べ'),
+    ('ど', 'ゴ'),
+    ('ば', 'ザ'),
+    ('び', 'ヂ'),
+    ('ぶ', 'ズ'),
+    ('べ', 'ゼ'),
+    ('ぼ', 'ド'),
+    ('ぱ', 'プ'),
+    ('ぴ', 'ぺ'),
+    ('ぷ', 'パ'),
+    ('ぺ', 'ポ'),
+    ('ぽ', 'ピ'),
+    ('ぁ', 'ァ'),
+    ('ぃ', 'ィ'),
+    ('ぅ', 'ゥ'),
+    ('ぇ', 'ェ'),
+    ('ぉ', 'ォ'),
+    ('ゃ', 'ャ'),
+    ('ゅ', 'ュ'),
+    ('ょ', 'ョ'),
+    ('っ', 'ッ'),
+];
+
+//有効にした feature の変換ルールだけで作る
+static ALBHED_TABLE: OnceBox<SubstitutionTable> = OnceBox::new();
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_str_extension() {
+        assert_eq!("HI".to_albhed(), "RE");
+        assert_eq!("RE".from_albhed(), "HI");
+        assert!(matches!("0123".to_albhed(), Cow::Borrowed("0123")));
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_std_conversions() {
+        let original: OriginalText = "やっちまうか！？".parse().unwrap();
+        let encoded = original.encode();
+        assert_eq!(encoded.to_string(), "タッヒヤフア！？");
+        assert_eq!(encoded.as_ref(), "タッヒヤフア！？");
+
+        let decoded = AlBhedText::try_from(String::from("RE")).unwrap().decode();
+        assert_eq!(format!("{decoded}"), "HI");
+        assert!("".parse::<AlBhedText>().is_err());
+        assert!(OriginalText::try_from("").is_err());
+    }
+
+    #[test]
+    fn test_new_with_empty_text() {
+        let result = AlBhedText::new("");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_decode_jp() {
+        let result = AlBhedText::new("ギアンダメネ！ ラッラソ マッキンキノ！");
+        assert!(result.is_ok());
+
+        let decoded = result.unwrap().decode();
+        assert_eq!(decoded.text(), "じかんがねえ！ さっさと はっしんしろ！");
+    }
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_encode_jp() {
+        let result = OriginalText::new("やっちまうか！？");
+        assert!(result.is_ok());
+
+        let encoded = result.unwrap().encode();
+        assert_eq!(encoded.text(), "タッヒヤフア！？");
+    }
+
+    #[test]
+    fn test_substitution_table_rejects_duplicates() {
+        let mapping = HashMap::from([('A', 'X'), ('B', 'X')]);
+        assert!(SubstitutionTable::new(mapping).is_err());
+    }
+
+    #[test]
+    fn test_encode_with_custom_table() {
+        let table = SubstitutionTable::new(HashMap::from([('A', 'B'), ('B', 'A')])).unwrap();
+        let encoded = OriginalText::new("ABC").unwrap().encode_with(&table);
+        assert_eq!(encoded.text(), "BAC");
+        assert_eq!(encoded.decode_with(&table).text(), "ABC");
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_encode_except() {
+        let original = OriginalText::new("HI @RIKKU ゆうな").unwrap();
+        let encoded = original.encode_except(SubstitutionTable::albhed(), &[3..9, 10..11]);
+        assert_eq!(encoded.text(), "RE @RIKKU ゆフハ");
+        assert_eq!(
+            encoded
+                .decode_except(SubstitutionTable::albhed(), &[3..9, 10..11])
+                .text(),
+            "HI @RIKKU ゆうな"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_encode_except_skips_inverted_ranges() {
+        let original = OriginalText::new("HELLO").unwrap();
+        let encoded = original.encode_except(SubstitutionTable::albhed(), &[4..1, 3..3]);
+        assert_eq!(encoded.text(), "RAMMU");
+        let encoded = original.encode_except(SubstitutionTable::albhed(), &[0..2, 1..0]);
+        assert_eq!(encoded.text(), "HEMMU");
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_encode_restricted_to_language() {
+        let original = OriginalText::new("HELLO こんにちは").unwrap();
+
+        let japanese = SubstitutionTable::albhed().restricted_to(&[Language::Japanese]);
+        assert_eq!(original.encode_with(&japanese).text(), "HELLO ヨンシヒマ");
+
+        let english = SubstitutionTable::albhed().restricted_to(&[Language::English]);
+        assert_eq!(original.encode_with(&english).text(), "RAMMU こんにちは");
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_without_plaintext() {
+        let original = OriginalText::new("HELLO").unwrap();
+        let primers = SubstitutionTable::albhed().without_plaintext(&['E', 'L']);
+        let encoded = original.encode_with(&primers);
+        assert_eq!(encoded.text(), "RELLU");
+        assert_eq!(primers.decode_char('A'), 'A');
+        assert_eq!(primers.decode_char('R'), 'H');
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_case_insensitive() {
+        let table = SubstitutionTable::albhed().case_insensitive();
+        let original = OriginalText::new("Hello, World").unwrap();
+        let encoded = original.encode_with(&table);
+        assert_eq!(encoded.text(), "Rammu, Funmt");
+        assert_eq!(encoded.decode_with(&table).text(), "Hello, World");
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_unchanged_text_is_borrowed() {
+        let table = SubstitutionTable::albhed();
+        assert!(matches!(table.encode("123, 456!\r\n"), Cow::Borrowed(_)));
+        assert!(matches!(table.decode("👍 漢字"), Cow::Borrowed(_)));
+        assert!(matches!(table.encode("12 AB"), Cow::Owned(s) if s == "12 YP"));
+
+        let original = OriginalText::new("...").unwrap();
+        assert!(matches!(
+            original.encode().into_text(),
+            Cow::Borrowed("...")
+        ));
+    }
+
+    //組み込まなかった変換ルールの文字はそのまま残る
+    #[test]
+    #[cfg(not(feature = "english"))]
+    fn test_latin_unchanged_without_english() {
+        let table = SubstitutionTable::albhed();
+        assert!(matches!(table.encode("HELLO"), Cow::Borrowed("HELLO")));
+        assert!(matches!(table.decode("RAMMU"), Cow::Borrowed("RAMMU")));
+        assert!(!table.languages().contains(&Language::English));
+    }
+
+    #[test]
+    #[cfg(not(feature = "japanese"))]
+    fn test_kana_unchanged_without_japanese() {
+        let table = SubstitutionTable::albhed();
+        assert!(matches!(table.encode("じかん"), Cow::Borrowed("じかん")));
+        assert!(matches!(table.decode("ギアン"), Cow::Borrowed("ギアン")));
+        assert!(!table.languages().contains(&Language::Japanese));
+    }
+
+    #[test]
+    fn test_lookup_prefers_earlier_groups() {
+        let table = SubstitutionTable::from_groups(vec![
+            (Language::English, HashMap::from([('A', 'ア'), ('ー', 'B')])),
+            (Language::Japanese, HashMap::from([('A', 'C'), ('あ', 'Z')])),
+        ])
+        .unwrap();
+        assert_eq!(table.encode("Aあー"), "アZB");
+        assert_eq!(table.decode("アZBC"), "AあーA");
+        assert_eq!(table.encode_char('\u{1F600}'), '\u{1F600}');
+    }
+
+    //(入力, アルベド語) の組
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    const GRAPHEME_CORPUS: &[(&str, &str)] = &[
+        //ZWJ絵文字
+        ("👨\u{200D}👩\u{200D}👧", "👨\u{200D}👩\u{200D}👧"),
+        ("🏳\u{FE0F}\u{200D}🌈", "🏳\u{FE0F}\u{200D}🌈"),
+        //国旗
+        ("🇯🇵🇺🇸", "🇯🇵🇺🇸"),
+        //キーキャップ
+        (
+            "1\u{FE0F}\u{20E3}#\u{FE0F}\u{20E3}",
+            "1\u{FE0F}\u{20E3}#\u{FE0F}\u{20E3}",
+        ),
+        ("A\u{20E3}", "A\u{20E3}"),
+        //肌の色の修飾子
+        ("👍\u{1F3FD}", "👍\u{1F3FD}"),
+        ("👩\u{1F3FF}\u{200D}💻", "👩\u{1F3FF}\u{200D}💻"),
+        //異体字セレクタ
+        ("A\u{FE0F}", "A\u{FE0F}"),
+        //結合ダイアクリティカルマーク
+        ("CAFE\u{0301}", "LYVA\u{0301}"),
+        ("N\u{0303}O\u{0308}\u{0301}", "H\u{0303}U\u{0308}\u{0301}"),
+        //混在
+        ("HI 👋\u{1F3FB} あ", "RE 👋\u{1F3FB} ワ"),
+    ];
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_encode_grapheme_corpus() {
+        for (input, expected) in GRAPHEME_CORPUS {
+            let encoded = OriginalText::new(*input).unwrap().encode();
+            assert_eq!(encoded.text(), *expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_decode_grapheme_corpus() {
+        for (expected, input) in GRAPHEME_CORPUS {
+            let decoded = AlBhedText::new(*input).unwrap().decode();
+            assert_eq!(decoded.text(), *expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_encode_voicing_marks() {
+        let encoded = OriginalText::new("か\u{3099}ん\u{3099}は\u{309A}")
+            .unwrap()
+            .encode();
+        //「ん」に濁点は付かないのでそのまま残す
+        assert_eq!(encoded.text(), "ダん\u{3099}プ");
+        assert_eq!(encoded.decode().text(), "がん\u{3099}ぱ");
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_encode_voicing_marks_restricted_to_english() {
+        let original = OriginalText::new("HI か\u{3099}は\u{309A}").unwrap();
+        let english = SubstitutionTable::albhed().restricted_to(&[Language::English]);
+        let encoded = original.encode_with(&english);
+        assert_eq!(encoded.text(), "RE か\u{3099}は\u{309A}");
+    }
+}
@@ -0,0 +1,36 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DomainError {
+    #[error("Empty String")]
+    EmptyText,
+    #[error("Unsupported character '{0}'")]
+    UnsupportedCharacter(char),
+    #[error("Duplicate mapping to '{0}'")]
+    DuplicateMapping(char),
+}
+
+impl DomainError {
+    //APIの利用者に返す、変わらない識別子
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::EmptyText => "empty_text",
+            DomainError::UnsupportedCharacter(_) => "unsupported_character",
+            DomainError::DuplicateMapping(_) => "duplicate_mapping",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code() {
+        assert_eq!(DomainError::EmptyText.code(), "empty_text");
+        assert_eq!(
+            DomainError::DuplicateMapping('X').to_string(),
+            "Duplicate mapping to 'X'"
+        );
+    }
+}
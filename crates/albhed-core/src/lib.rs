@@ -0,0 +1,8 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod entities;
+pub mod error;
+#[cfg(feature = "serde")]
+pub mod serde_albhed;
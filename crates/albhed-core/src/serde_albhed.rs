@@ -0,0 +1,88 @@
+use alloc::string::String;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::entities::{AlBhedExt, AlBhedText, OriginalText};
+
+impl Serialize for AlBhedText<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.text())
+    }
+}
+
+impl<'de> Deserialize<'de> for AlBhedText<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        AlBhedText::new(text).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for OriginalText<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.text())
+    }
+}
+
+impl<'de> Deserialize<'de> for OriginalText<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        OriginalText::new(text).map_err(D::Error::custom)
+    }
+}
+
+//#[serde(with = "albhed_core::serde_albhed")] を付けたフィールドは暗号文で保存し、読み込み時に復号する
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<str> + ?Sized,
+    S: Serializer,
+{
+    serializer.serialize_str(&value.as_ref().to_albhed())
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: From<String>,
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    Ok(T::from(text.from_albhed().into_owned()))
+}
+
+#[cfg(all(test, feature = "english"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_field_is_stored_enciphered() {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Save {
+            name: String,
+            #[serde(with = "crate::serde_albhed")]
+            secret: String,
+        }
+
+        let save = Save {
+            name: String::from("YUNA"),
+            secret: String::from("HI じかん"),
+        };
+
+        let json = serde_json::to_string(&save).unwrap();
+        assert_eq!(json, r#"{"name":"YUNA","secret":"RE ギアン"}"#);
+        assert_eq!(serde_json::from_str::<Save>(&json).unwrap(), save);
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let encoded = OriginalText::new("HI").unwrap().encode();
+        let json = serde_json::to_string(&encoded).unwrap();
+        assert_eq!(json, r#""RE""#);
+
+        let restored: AlBhedText = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.decode().text(), "HI");
+        assert!(serde_json::from_str::<OriginalText>(r#""""#).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use std::env;
+use std::path::PathBuf;
+
+//C から使うためのヘッダーを OUT_DIR に書き出す
+//リポジトリの include/albhed.h と同じであることは tests/header.rs で確かめる
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir.join("src/lib.rs"))
+        .generate()
+        .expect("Unable to generate C header")
+        .write_to_file(out_dir.join("albhed.h"));
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
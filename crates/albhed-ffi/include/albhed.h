@@ -0,0 +1,98 @@
+#ifndef ALBHED_H
+#define ALBHED_H
+
+/* Generated by cbindgen from src/lib.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status code returned by every fallible albhed_* function.
+ */
+typedef enum AlbhedStatus {
+  ALBHED_STATUS_OK = 0,
+  ALBHED_STATUS_NULL_POINTER = 1,
+  ALBHED_STATUS_INVALID_UTF8 = 2,
+  ALBHED_STATUS_INTERIOR_NUL = 3,
+} AlbhedStatus;
+
+/**
+ * Opaque translator handle. Create with albhed_translator_new and release with
+ * albhed_translator_free.
+ */
+typedef struct AlbhedTranslator AlbhedTranslator;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a translator using the built-in Al Bhed table.
+ */
+struct AlbhedTranslator *albhed_translator_new(void);
+
+/**
+ * Releases a translator. Passing NULL is a no-op.
+ *
+ * # Safety
+ *
+ * `translator` must be NULL or a pointer returned by albhed_translator_new that
+ * has not been freed yet.
+ */
+void albhed_translator_free(struct AlbhedTranslator *translator);
+
+/**
+ * Enciphers `input_len` bytes of UTF-8 at `input`. On success `*output` receives
+ * a NUL-terminated string that must be released with albhed_string_free, and
+ * `*output_len` (if not NULL) receives its length in bytes without the NUL.
+ *
+ * # Safety
+ *
+ * `translator` must come from albhed_translator_new, `input` must point to
+ * `input_len` readable bytes (it may be NULL when `input_len` is 0), and
+ * `output` and `output_len` must be NULL or valid for writes.
+ */
+enum AlbhedStatus albhed_encode(const struct AlbhedTranslator *translator,
+                                const uint8_t *input,
+                                size_t input_len,
+                                char **output,
+                                size_t *output_len);
+
+/**
+ * Deciphers `input_len` bytes of UTF-8 at `input`. Ownership of the result is
+ * the same as for albhed_encode.
+ *
+ * # Safety
+ *
+ * Same requirements as albhed_encode.
+ */
+enum AlbhedStatus albhed_decode(const struct AlbhedTranslator *translator,
+                                const uint8_t *input,
+                                size_t input_len,
+                                char **output,
+                                size_t *output_len);
+
+/**
+ * Releases a string returned by albhed_encode or albhed_decode. Passing NULL is
+ * a no-op.
+ *
+ * # Safety
+ *
+ * `string` must be NULL or a result string that has not been freed yet.
+ */
+void albhed_string_free(char *string);
+
+/**
+ * Returns a static, NUL-terminated description of a status code. Codes that are
+ * not an AlbhedStatus yield "Unknown status".
+ */
+const char *albhed_status_message(int32_t status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ALBHED_H */
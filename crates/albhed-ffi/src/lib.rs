@@ -0,0 +1,245 @@
+use std::borrow::Cow;
+use std::ffi::{c_char, CStr, CString};
+use std::{ptr, slice, str};
+
+use albhed_core::entities::SubstitutionTable;
+
+/// Status code returned by every fallible albhed_* function.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlbhedStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InteriorNul = 3,
+}
+
+impl TryFrom<i32> for AlbhedStatus {
+    type Error = i32;
+
+    fn try_from(code: i32) -> Result<Self, Self::Error> {
+        [
+            AlbhedStatus::Ok,
+            AlbhedStatus::NullPointer,
+            AlbhedStatus::InvalidUtf8,
+            AlbhedStatus::InteriorNul,
+        ]
+        .into_iter()
+        .find(|&status| status as i32 == code)
+        .ok_or(code)
+    }
+}
+
+/// Opaque translator handle. Create with albhed_translator_new and release with
+/// albhed_translator_free.
+pub struct AlbhedTranslator {
+    table: &'static SubstitutionTable,
+}
+
+/// Creates a translator using the built-in Al Bhed table.
+#[no_mangle]
+pub extern "C" fn albhed_translator_new() -> *mut AlbhedTranslator {
+    Box::into_raw(Box::new(AlbhedTranslator {
+        table: SubstitutionTable::albhed(),
+    }))
+}
+
+/// Releases a translator. Passing NULL is a no-op.
+///
+/// # Safety
+///
+/// `translator` must be NULL or a pointer returned by albhed_translator_new that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn albhed_translator_free(translator: *mut AlbhedTranslator) {
+    if !translator.is_null() {
+        drop(Box::from_raw(translator));
+    }
+}
+
+/// Enciphers `input_len` bytes of UTF-8 at `input`. On success `*output` receives
+/// a NUL-terminated string that must be released with albhed_string_free, and
+/// `*output_len` (if not NULL) receives its length in bytes without the NUL.
+///
+/// # Safety
+///
+/// `translator` must come from albhed_translator_new, `input` must point to
+/// `input_len` readable bytes (it may be NULL when `input_len` is 0), and
+/// `output` and `output_len` must be NULL or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn albhed_encode(
+    translator: *const AlbhedTranslator,
+    input: *const u8,
+    input_len: usize,
+    output: *mut *mut c_char,
+    output_len: *mut usize,
+) -> AlbhedStatus {
+    translate(
+        translator,
+        input,
+        input_len,
+        output,
+        output_len,
+        |table, text| table.encode(text),
+    )
+}
+
+/// Deciphers `input_len` bytes of UTF-8 at `input`. Ownership of the result is
+/// the same as for albhed_encode.
+///
+/// # Safety
+///
+/// Same requirements as albhed_encode.
+#[no_mangle]
+pub unsafe extern "C" fn albhed_decode(
+    translator: *const AlbhedTranslator,
+    input: *const u8,
+    input_len: usize,
+    output: *mut *mut c_char,
+    output_len: *mut usize,
+) -> AlbhedStatus {
+    translate(
+        translator,
+        input,
+        input_len,
+        output,
+        output_len,
+        |table, text| table.decode(text),
+    )
+}
+
+/// Releases a string returned by albhed_encode or albhed_decode. Passing NULL is
+/// a no-op.
+///
+/// # Safety
+///
+/// `string` must be NULL or a result string that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn albhed_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Returns a static, NUL-terminated description of a status code. Codes that are
+/// not an AlbhedStatus yield "Unknown status".
+#[no_mangle]
+pub extern "C" fn albhed_status_message(status: i32) -> *const c_char {
+    //C からはどんな整数でも渡せるので、列挙型として受け取らない
+    let message: &'static CStr = match AlbhedStatus::try_from(status) {
+        Ok(AlbhedStatus::Ok) => c"OK",
+        Ok(AlbhedStatus::NullPointer) => c"Null pointer",
+        Ok(AlbhedStatus::InvalidUtf8) => c"Invalid UTF-8",
+        Ok(AlbhedStatus::InteriorNul) => c"Text contains a NUL character",
+        Err(_) => c"Unknown status",
+    };
+    message.as_ptr()
+}
+
+//C から渡されたバッファを検証して変換し、結果を NUL 終端の文字列で返す
+unsafe fn translate(
+    translator: *const AlbhedTranslator,
+    input: *const u8,
+    input_len: usize,
+    output: *mut *mut c_char,
+    output_len: *mut usize,
+    convert: impl for<'a> Fn(&SubstitutionTable, &'a str) -> Cow<'a, str>,
+) -> AlbhedStatus {
+    if output.is_null() || translator.is_null() {
+        return AlbhedStatus::NullPointer;
+    }
+    *output = ptr::null_mut();
+    if !output_len.is_null() {
+        *output_len = 0;
+    }
+
+    let bytes = match input_len {
+        0 => &[][..],
+        _ if input.is_null() => return AlbhedStatus::NullPointer,
+        _ => slice::from_raw_parts(input, input_len),
+    };
+    let Ok(text) = str::from_utf8(bytes) else {
+        return AlbhedStatus::InvalidUtf8;
+    };
+    let Ok(translated) = CString::new(convert((*translator).table, text).into_owned()) else {
+        return AlbhedStatus::InteriorNul;
+    };
+
+    if !output_len.is_null() {
+        *output_len = translated.as_bytes().len();
+    }
+    *output = translated.into_raw();
+    AlbhedStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn call(
+        function: unsafe extern "C" fn(
+            *const AlbhedTranslator,
+            *const u8,
+            usize,
+            *mut *mut c_char,
+            *mut usize,
+        ) -> AlbhedStatus,
+        input: &[u8],
+    ) -> Result<String, AlbhedStatus> {
+        let translator = albhed_translator_new();
+        let mut output = ptr::null_mut();
+        let mut output_len = 0;
+        let status = function(
+            translator,
+            input.as_ptr(),
+            input.len(),
+            &mut output,
+            &mut output_len,
+        );
+        albhed_translator_free(translator);
+        if status != AlbhedStatus::Ok {
+            assert!(output.is_null());
+            return Err(status);
+        }
+
+        let text = CStr::from_ptr(output).to_str().unwrap().to_string();
+        assert_eq!(text.len(), output_len);
+        albhed_string_free(output);
+        Ok(text)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        unsafe {
+            let encoded = call(albhed_encode, "HELLO じかん".as_bytes()).unwrap();
+            assert_eq!(encoded, "RAMMU ギアン");
+            let decoded = call(albhed_decode, encoded.as_bytes()).unwrap();
+            assert_eq!(decoded, "HELLO じかん");
+            assert_eq!(call(albhed_encode, b"").unwrap(), "");
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            assert_eq!(
+                call(albhed_encode, b"\xff\xfe"),
+                Err(AlbhedStatus::InvalidUtf8)
+            );
+            assert_eq!(
+                call(albhed_decode, b"RE\0RE"),
+                Err(AlbhedStatus::InteriorNul)
+            );
+
+            let mut output = ptr::null_mut();
+            let status =
+                albhed_encode(ptr::null(), b"HI".as_ptr(), 2, &mut output, ptr::null_mut());
+            assert_eq!(status, AlbhedStatus::NullPointer);
+
+            let message = CStr::from_ptr(albhed_status_message(status as i32));
+            assert_eq!(message.to_str().unwrap(), "Null pointer");
+            let message = CStr::from_ptr(albhed_status_message(42));
+            assert_eq!(message.to_str().unwrap(), "Unknown status");
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+//tests/roundtrip.c を cdylib とリンクして実行する
+#[test]
+fn test_c_roundtrip() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    //cargo test が作る cdylib は、このテストと同じ target/<profile>/deps にある
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap();
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("roundtrip");
+
+    let compiled = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("tests/roundtrip.c"))
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lalbhed_ffi")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(compiled.success());
+
+    //cargo が渡す LD_LIBRARY_PATH には target/<profile> も入っていて、
+    //cargo build で作った古い cdylib を rpath より先に読んでしまう
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", lib_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
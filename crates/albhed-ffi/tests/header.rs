@@ -0,0 +1,16 @@
+use std::fs;
+use std::path::PathBuf;
+
+//build.rs が生成したヘッダーと、配布する include/albhed.h が一致していること
+#[test]
+fn test_header_is_up_to_date() {
+    let generated = PathBuf::from(env!("OUT_DIR")).join("albhed.h");
+    let committed = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("include/albhed.h");
+
+    assert!(
+        fs::read_to_string(&generated).unwrap() == fs::read_to_string(&committed).unwrap(),
+        "{} is out of date; copy {} over it",
+        committed.display(),
+        generated.display()
+    );
+}
@@ -0,0 +1,57 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "albhed.h"
+
+static int check(const char *label, const char *actual, const char *expected) {
+    if (strcmp(actual, expected) != 0) {
+        fprintf(stderr, "%s: expected \"%s\", got \"%s\"\n", label, expected, actual);
+        return 1;
+    }
+    return 0;
+}
+
+int main(void) {
+    const char *original = "WHAT IS THAT? じかんがねえ！";
+    int failures = 0;
+
+    AlbhedTranslator *translator = albhed_translator_new();
+    if (translator == NULL) {
+        fprintf(stderr, "albhed_translator_new returned NULL\n");
+        return 1;
+    }
+
+    char *encoded = NULL;
+    size_t encoded_len = 0;
+    AlbhedStatus status = albhed_encode(translator, (const uint8_t *)original, strlen(original),
+                                        &encoded, &encoded_len);
+    if (status != ALBHED_STATUS_OK) {
+        fprintf(stderr, "albhed_encode: %s\n", albhed_status_message(status));
+        return 1;
+    }
+    failures += check("encode", encoded, "FRYD EC DRYD? ギアンダメネ！");
+    failures += encoded_len != strlen(encoded);
+
+    char *decoded = NULL;
+    status = albhed_decode(translator, (const uint8_t *)encoded, encoded_len, &decoded, NULL);
+    if (status != ALBHED_STATUS_OK) {
+        fprintf(stderr, "albhed_decode: %s\n", albhed_status_message(status));
+        return 1;
+    }
+    failures += check("decode", decoded, original);
+
+    char *invalid = NULL;
+    status = albhed_encode(translator, (const uint8_t *)"\xff", 1, &invalid, NULL);
+    failures += status != ALBHED_STATUS_INVALID_UTF8 || invalid != NULL;
+    failures += check("message", albhed_status_message(status), "Invalid UTF-8");
+    failures += check("unknown message", albhed_status_message(-1), "Unknown status");
+
+    albhed_string_free(encoded);
+    albhed_string_free(decoded);
+    albhed_translator_free(translator);
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+
+use albhed_core::entities::{Language, SubstitutionTable};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+const DEFAULT_TABLE: &str = "albhed";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CaseMode {
+    //表にある文字だけを変換する
+    Exact,
+    //小文字も変換し、大文字・小文字はそのまま残す
+    Preserve,
+}
+
+struct Options {
+    text: LitStr,
+    table: Option<LitStr>,
+    case: CaseMode,
+    strict: bool,
+}
+
+//albhed!("text", table = "albhed", case = "preserve", strict)
+//table は albhed（英語と日本語）、english、japanese のどれか
+impl Parse for Options {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut options = Options {
+            text: input.parse()?,
+            table: None,
+            case: CaseMode::Exact,
+            strict: false,
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: Ident = input.parse()?;
+            match name.to_string().as_str() {
+                "strict" => options.strict = true,
+                "table" => {
+                    input.parse::<Token![=]>()?;
+                    options.table = Some(input.parse()?);
+                }
+                "case" => {
+                    input.parse::<Token![=]>()?;
+                    let value: LitStr = input.parse()?;
+                    options.case = match value.value().as_str() {
+                        "exact" => CaseMode::Exact,
+                        "preserve" => CaseMode::Preserve,
+                        other => {
+                            return Err(syn::Error::new(
+                                value.span(),
+                                format!("Unknown case mode: {other}"),
+                            ))
+                        }
+                    };
+                }
+                other => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("Unknown option: {other}"),
+                    ))
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+//コンパイル時にアルベド語へ変換し、&'static str のリテラルに展開する
+#[proc_macro]
+pub fn albhed(input: TokenStream) -> TokenStream {
+    expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: TokenStream2) -> syn::Result<TokenStream2> {
+    let options: Options = syn::parse2(input)?;
+    let text = options.text.value();
+
+    let albhed = SubstitutionTable::albhed();
+    let mut table = match &options.table {
+        None => Cow::Borrowed(albhed),
+        Some(name) => match name.value().as_str() {
+            DEFAULT_TABLE => Cow::Borrowed(albhed),
+            "english" => Cow::Owned(albhed.restricted_to(&[Language::English])),
+            "japanese" => Cow::Owned(albhed.restricted_to(&[Language::Japanese])),
+            other => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Unknown table: {other}"),
+                ))
+            }
+        },
+    };
+    if options.case == CaseMode::Preserve {
+        table = Cow::Owned(table.case_insensitive());
+    }
+
+    //strict では、表にない文字が残るとコンパイルエラーにする（位置は文字単位）
+    if options.strict {
+        let unsupported = text
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| c.is_alphabetic() && table.encode_char(c) == c);
+        if let Some((position, c)) = unsupported {
+            return Err(syn::Error::new(
+                options.text.span(),
+                format!("Unsupported character '{c}' at {position}"),
+            ));
+        }
+    }
+
+    let encoded = LitStr::new(&table.encode(&text), options.text.span());
+    Ok(quote!(#encoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_to_string(input: TokenStream2) -> String {
+        let output: LitStr = syn::parse2(expand(input).unwrap()).unwrap();
+        output.value()
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(expand_to_string(quote!("WHAT IS THAT?")), "FRYD EC DRYD?");
+        assert_eq!(
+            expand_to_string(quote!("じかんがねえ！", table = "albhed")),
+            "ギアンダメネ！"
+        );
+        assert_eq!(
+            expand_to_string(quote!("HI やあ", table = "english")),
+            "RE やあ"
+        );
+        assert_eq!(
+            expand_to_string(quote!("HI やあ", table = "japanese")),
+            "HI タワ"
+        );
+        assert_eq!(
+            expand_to_string(quote!("Hi やあ", table = "english", case = "preserve")),
+            "Re やあ"
+        );
+        assert_eq!(expand_to_string(quote!("Hi", case = "exact")), "Ri");
+        assert_eq!(
+            expand_to_string(quote!("Hi", case = "preserve", strict,)),
+            "Re"
+        );
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        for (input, message) in [
+            (quote!("Hi", strict), "Unsupported character 'i' at 1"),
+            (quote!("漢字", strict), "Unsupported character '漢' at 0"),
+            (quote!("やあ Hi", strict), "Unsupported character 'i' at 4"),
+            (
+                quote!("HI やあ", table = "english", strict),
+                "Unsupported character 'や' at 3",
+            ),
+            (quote!("HI", table = "keyed"), "Unknown table: keyed"),
+            (quote!("HI", case = "upper"), "Unknown case mode: upper"),
+            (quote!("HI", decode), "Unknown option: decode"),
+            (quote!(42), "expected string literal"),
+        ] {
+            assert_eq!(expand(input).unwrap_err().to_string(), message);
+        }
+    }
+}
@@ -0,0 +1,7 @@
+#[test]
+fn test_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass.rs");
+    cases.compile_fail("tests/ui/strict.rs");
+    cases.compile_fail("tests/ui/unknown_table.rs");
+}
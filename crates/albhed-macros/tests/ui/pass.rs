@@ -0,0 +1,8 @@
+use albhed_macros::albhed;
+
+const GREETING: &str = albhed!("Hello", case = "preserve", strict);
+
+fn main() {
+    assert_eq!(GREETING, "Rammu");
+    assert_eq!(albhed!("HI やあ", table = "japanese"), "HI タワ");
+}
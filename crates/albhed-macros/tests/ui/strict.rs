@@ -0,0 +1,5 @@
+use albhed_macros::albhed;
+
+fn main() {
+    let _ = albhed!("やあ Hi", strict);
+}
@@ -0,0 +1,260 @@
+from typing import Literal, Optional, Sequence
+
+Direction = Literal["encode", "decode"]
+CaseMode = Literal["exact", "preserve"]
+ScriptName = Literal["latin", "kana"]
+LanguageName = Literal["english", "japanese"]
+EntityKind = Literal["code", "url", "email", "placeholder", "mention", "hashtag"]
+Markup = Literal["strip", "keep"]
+LintMode = Literal["off", "report", "normalize", "reject"]
+TableKind = Literal["builtin", "keyed", "plugin", "custom"]
+
+class AlBhedError(ValueError):
+    code: str
+
+class Table:
+    def __init__(self, mapping: dict[str, str]) -> None: ...
+    @staticmethod
+    def albhed() -> Table: ...
+    @staticmethod
+    def from_groups(groups: Sequence[tuple[LanguageName, dict[str, str]]]) -> Table: ...
+    def restricted_to(self, languages: Sequence[LanguageName]) -> Table: ...
+    def without_plaintext(self, letters: str) -> Table: ...
+    def case_insensitive(self) -> Table: ...
+    @property
+    def languages(self) -> list[LanguageName]: ...
+    def pairs(self) -> list[tuple[str, str]]: ...
+    def encode_char(self, c: str) -> str: ...
+    def decode_char(self, c: str) -> str: ...
+    def encode(self, text: str) -> str: ...
+    def decode(self, text: str) -> str: ...
+    # excluded ranges are [start, end) character indices, as in str slicing;
+    # a range with start > end raises ValueError
+    def encode_except(self, text: str, excluded: Sequence[tuple[int, int]]) -> str: ...
+    def decode_except(self, text: str, excluded: Sequence[tuple[int, int]]) -> str: ...
+
+class TableInfo:
+    @property
+    def name(self) -> str: ...
+    @property
+    def kind(self) -> TableKind: ...
+    @property
+    def description(self) -> str: ...
+    @property
+    def scripts(self) -> list[ScriptName]: ...
+    @property
+    def size(self) -> int: ...
+
+class TableRegistry:
+    def __init__(self) -> None: ...
+    def tables(self) -> list[TableInfo]: ...
+    def info(self, name: str) -> Optional[TableInfo]: ...
+    def get(self, name: str) -> Optional[Table]: ...
+    # raises AlBhedError if the name is already taken
+    def register(self, name: str, table: Table, description: str = "Custom table") -> None: ...
+    def generate(
+        self,
+        passphrase: str,
+        script: Literal["latin", "kana", "both"] = "both",
+        allow_self_mapping: bool = False,
+    ) -> str: ...
+    def alphabet(self, name: str) -> dict[ScriptName, list[tuple[str, str]]]: ...
+    def __contains__(self, name: str) -> bool: ...
+
+class LintFinding:
+    @property
+    def kind(self) -> Literal["bidi_control", "zero_width", "homoglyph"]: ...
+    @property
+    def position(self) -> int: ...
+    @property
+    def character(self) -> str: ...
+    @property
+    def replacement(self) -> Optional[str]: ...
+    @property
+    def code_point(self) -> str: ...
+
+class Suggestion:
+    @property
+    def text(self) -> str: ...
+    @property
+    def decoded(self) -> str: ...
+    @property
+    def distance(self) -> int: ...
+
+class Misspelling:
+    @property
+    def start(self) -> int: ...
+    @property
+    def end(self) -> int: ...
+    @property
+    def text(self) -> str: ...
+    @property
+    def decoded(self) -> str: ...
+    @property
+    def suggestions(self) -> list[Suggestion]: ...
+
+class ProtectedSpan:
+    @property
+    def kind(self) -> EntityKind: ...
+    @property
+    def start(self) -> int: ...
+    @property
+    def end(self) -> int: ...
+    @property
+    def text(self) -> str: ...
+
+class Pronunciation:
+    @property
+    def start(self) -> int: ...
+    @property
+    def end(self) -> int: ...
+    @property
+    def word(self) -> str: ...
+    @property
+    def syllables(self) -> list[str]: ...
+    @property
+    def respelling(self) -> str: ...
+    @property
+    def ipa(self) -> str: ...
+
+class TextChange:
+    @property
+    def start(self) -> int: ...
+    @property
+    def end(self) -> int: ...
+    @property
+    def before(self) -> str: ...
+    @property
+    def after(self) -> str: ...
+
+class StageReport:
+    @property
+    def stage(self) -> str: ...
+    @property
+    def phase(self) -> Literal["pre", "post"]: ...
+    @property
+    def changes(self) -> list[TextChange]: ...
+
+# Positions in results are reported exactly as by the HTTP API.
+class Translation:
+    @property
+    def text(self) -> str: ...
+    @property
+    def direction(self) -> Direction: ...
+    @property
+    def table(self) -> str: ...
+    @property
+    def kind(self) -> TableKind: ...
+    @property
+    def scripts(self) -> list[ScriptName]: ...
+    @property
+    def lint(self) -> list[LintFinding]: ...
+    @property
+    def ignored_options(self) -> list[str]: ...
+    @property
+    def protected(self) -> Optional[list[ProtectedSpan]]: ...
+    @property
+    def pronunciation(self) -> Optional[list[Pronunciation]]: ...
+    @property
+    def ssml(self) -> Optional[str]: ...
+    @property
+    def stages(self) -> list[StageReport]: ...
+
+class Translator:
+    def __init__(
+        self,
+        registry: Optional[TableRegistry] = None,
+        parallel_threshold: Optional[int] = None,
+    ) -> None: ...
+    def translate(
+        self,
+        text: str,
+        direction: Direction = "encode",
+        *,
+        table: Optional[str] = None,
+        case: Optional[CaseMode] = None,
+        scripts: Optional[Sequence[ScriptName]] = None,
+        protect: Optional[Sequence[EntityKind]] = None,
+        markup: Optional[Markup] = None,
+        lint: Optional[LintMode] = None,
+        primers: Optional[str] = None,
+        pronounce: bool = False,
+        ssml: Optional[str] = None,
+        ssml_hints: bool = False,
+        pipeline: Optional[str] = None,
+        pre: Sequence[str] = (),
+        post: Sequence[str] = (),
+    ) -> Translation: ...
+    def encode(
+        self,
+        text: str,
+        *,
+        table: Optional[str] = None,
+        case: Optional[CaseMode] = None,
+        scripts: Optional[Sequence[ScriptName]] = None,
+        protect: Optional[Sequence[EntityKind]] = None,
+        markup: Optional[Markup] = None,
+        lint: Optional[LintMode] = None,
+        primers: Optional[str] = None,
+        pronounce: bool = False,
+        ssml: Optional[str] = None,
+        ssml_hints: bool = False,
+        pipeline: Optional[str] = None,
+        pre: Sequence[str] = (),
+        post: Sequence[str] = (),
+    ) -> Translation: ...
+    def decode(
+        self,
+        text: str,
+        *,
+        table: Optional[str] = None,
+        case: Optional[CaseMode] = None,
+        scripts: Optional[Sequence[ScriptName]] = None,
+        protect: Optional[Sequence[EntityKind]] = None,
+        markup: Optional[Markup] = None,
+        lint: Optional[LintMode] = None,
+        primers: Optional[str] = None,
+        pronounce: bool = False,
+        ssml: Optional[str] = None,
+        ssml_hints: bool = False,
+        pipeline: Optional[str] = None,
+        pre: Sequence[str] = (),
+        post: Sequence[str] = (),
+    ) -> Translation: ...
+
+def encode(
+    text: str,
+    *,
+    table: Optional[str] = None,
+    case: Optional[CaseMode] = None,
+    scripts: Optional[Sequence[ScriptName]] = None,
+    protect: Optional[Sequence[EntityKind]] = None,
+    markup: Optional[Markup] = None,
+    lint: Optional[LintMode] = None,
+    primers: Optional[str] = None,
+    pronounce: bool = False,
+    ssml: Optional[str] = None,
+    ssml_hints: bool = False,
+    pipeline: Optional[str] = None,
+    pre: Sequence[str] = (),
+    post: Sequence[str] = (),
+) -> Translation: ...
+def decode(
+    text: str,
+    *,
+    table: Optional[str] = None,
+    case: Optional[CaseMode] = None,
+    scripts: Optional[Sequence[ScriptName]] = None,
+    protect: Optional[Sequence[EntityKind]] = None,
+    markup: Optional[Markup] = None,
+    lint: Optional[LintMode] = None,
+    primers: Optional[str] = None,
+    pronounce: bool = False,
+    ssml: Optional[str] = None,
+    ssml_hints: bool = False,
+    pipeline: Optional[str] = None,
+    pre: Sequence[str] = (),
+    post: Sequence[str] = (),
+) -> Translation: ...
+def lint(text: str) -> list[LintFinding]: ...
+def spellcheck(text: str) -> list[Misspelling]: ...
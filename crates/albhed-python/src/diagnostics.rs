@@ -0,0 +1,125 @@
+use albhed_usecase::domain::lint::{find_suspicious, LintFinding, LintKind};
+use albhed_usecase::domain::spellcheck::{Misspelling, Suggestion};
+use albhed_usecase::usecase::spellcheck_usecase::{
+    SpellcheckInputData, SpellcheckInputPort, SpellcheckInteractor,
+};
+use pyo3::prelude::*;
+
+use crate::usecase_error;
+
+#[pyclass(frozen, get_all, module = "albhed", name = "LintFinding")]
+#[derive(Clone)]
+pub struct PyLintFinding {
+    kind: &'static str,
+    position: usize,
+    character: char,
+    replacement: Option<char>,
+}
+
+impl From<&LintFinding> for PyLintFinding {
+    fn from(finding: &LintFinding) -> Self {
+        PyLintFinding {
+            kind: match finding.kind() {
+                LintKind::BidiControl => "bidi_control",
+                LintKind::ZeroWidth => "zero_width",
+                LintKind::Homoglyph => "homoglyph",
+            },
+            position: finding.position(),
+            character: finding.character(),
+            replacement: finding.replacement(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyLintFinding {
+    //制御文字は見えないので U+XXXX で表す
+    #[getter]
+    fn code_point(&self) -> String {
+        format!("U+{:04X}", self.character as u32)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LintFinding(kind={:?}, position={}, code_point={:?})",
+            self.kind,
+            self.position,
+            self.code_point()
+        )
+    }
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "Suggestion")]
+#[derive(Clone)]
+pub struct PySuggestion {
+    text: String,
+    decoded: String,
+    distance: usize,
+}
+
+impl From<&Suggestion> for PySuggestion {
+    fn from(suggestion: &Suggestion) -> Self {
+        PySuggestion {
+            text: suggestion.text().to_string(),
+            decoded: suggestion.decoded().to_string(),
+            distance: suggestion.distance(),
+        }
+    }
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "Misspelling")]
+#[derive(Clone)]
+pub struct PyMisspelling {
+    start: usize,
+    end: usize,
+    text: String,
+    decoded: String,
+    suggestions: Vec<PySuggestion>,
+}
+
+impl From<&Misspelling> for PyMisspelling {
+    fn from(misspelling: &Misspelling) -> Self {
+        PyMisspelling {
+            start: misspelling.start(),
+            end: misspelling.end(),
+            text: misspelling.text().to_string(),
+            decoded: misspelling.decoded().to_string(),
+            suggestions: misspelling
+                .suggestions()
+                .iter()
+                .map(PySuggestion::from)
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyMisspelling {
+    fn __repr__(&self) -> String {
+        format!(
+            "Misspelling(text={:?}, decoded={:?}, start={}, end={})",
+            self.text, self.decoded, self.start, self.end
+        )
+    }
+}
+
+//双方向制御文字・ゼロ幅文字・紛らわしい文字を探す
+#[pyfunction]
+pub fn lint(text: &str) -> Vec<PyLintFinding> {
+    find_suspicious(text)
+        .iter()
+        .map(PyLintFinding::from)
+        .collect()
+}
+
+#[pyfunction]
+pub fn spellcheck(py: Python<'_>, text: &str) -> PyResult<Vec<PyMisspelling>> {
+    let checked = py
+        .allow_threads(|| SpellcheckInteractor::new().spellcheck(SpellcheckInputData::new(text)))
+        .map_err(usecase_error)?;
+    Ok(checked
+        .get_misspellings()
+        .iter()
+        .map(PyMisspelling::from)
+        .collect())
+}
@@ -0,0 +1,94 @@
+use albhed_usecase::domain::entities::{Language, Script};
+use albhed_usecase::domain::error::DomainError;
+use albhed_usecase::domain::table_registry::TableKind;
+use albhed_usecase::usecase::error::UsecaseError;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+mod diagnostics;
+mod table;
+mod translate;
+
+create_exception!(albhed, AlBhedError, PyValueError);
+
+//HTTP の problem+json と同じ code を例外の属性にも付ける
+fn usecase_error(error: UsecaseError) -> PyErr {
+    Python::with_gil(|py| {
+        let exception = AlBhedError::new_err(error.to_string());
+        match exception.value(py).setattr("code", error.code()) {
+            Ok(()) => exception,
+            Err(err) => err,
+        }
+    })
+}
+
+fn domain_error(error: impl Into<DomainError>) -> PyErr {
+    usecase_error(UsecaseError::Domain(error.into()))
+}
+
+fn unknown(option: &str, value: &str) -> PyErr {
+    PyValueError::new_err(format!("Unknown {option}: {value}"))
+}
+
+//Python 側の名前は HTTP API の JSON と揃える
+fn parse_script(name: &str) -> PyResult<Script> {
+    match name {
+        "latin" => Ok(Script::Latin),
+        "kana" => Ok(Script::Kana),
+        _ => Err(unknown("script", name)),
+    }
+}
+
+fn script_name(script: Script) -> &'static str {
+    match script {
+        Script::Latin => "latin",
+        Script::Kana => "kana",
+    }
+}
+
+fn parse_language(name: &str) -> PyResult<Language> {
+    match name {
+        "english" => Ok(Language::English),
+        "japanese" => Ok(Language::Japanese),
+        _ => Err(unknown("language", name)),
+    }
+}
+
+fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::English => "english",
+        Language::Japanese => "japanese",
+    }
+}
+
+fn kind_name(kind: TableKind) -> &'static str {
+    match kind {
+        TableKind::Builtin => "builtin",
+        TableKind::Keyed => "keyed",
+        TableKind::Plugin => "plugin",
+        TableKind::Custom => "custom",
+    }
+}
+
+#[pymodule]
+fn albhed(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("AlBhedError", m.py().get_type::<AlBhedError>())?;
+    m.add_class::<table::PyTable>()?;
+    m.add_class::<table::PyTableInfo>()?;
+    m.add_class::<table::PyTableRegistry>()?;
+    m.add_class::<translate::PyTranslator>()?;
+    m.add_class::<translate::PyTranslation>()?;
+    m.add_class::<translate::PyProtectedSpan>()?;
+    m.add_class::<translate::PyPronunciation>()?;
+    m.add_class::<translate::PyStageReport>()?;
+    m.add_class::<translate::PyTextChange>()?;
+    m.add_class::<diagnostics::PyLintFinding>()?;
+    m.add_class::<diagnostics::PyMisspelling>()?;
+    m.add_class::<diagnostics::PySuggestion>()?;
+    m.add_function(wrap_pyfunction!(translate::encode, m)?)?;
+    m.add_function(wrap_pyfunction!(translate::decode, m)?)?;
+    m.add_function(wrap_pyfunction!(diagnostics::lint, m)?)?;
+    m.add_function(wrap_pyfunction!(diagnostics::spellcheck, m)?)?;
+    Ok(())
+}
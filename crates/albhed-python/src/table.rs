@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use albhed_usecase::domain::entities::{AlBhedText, OriginalText, Script, SubstitutionTable};
+use albhed_usecase::domain::table_registry::{TableInfo, TableKind, TableRegistry};
+use albhed_usecase::usecase::cipher_usecase::{
+    CipherAlphabetInputData, CipherInputPort, CipherInteractor,
+};
+use albhed_usecase::usecase::table_usecase::{TableInputData, TableInputPort, TableInteractor};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::{
+    domain_error, kind_name, language_name, parse_language, parse_script, script_name,
+    usecase_error,
+};
+
+#[pyclass(frozen, module = "albhed", name = "Table")]
+#[derive(Clone)]
+pub struct PyTable {
+    table: Arc<SubstitutionTable>,
+}
+
+impl PyTable {
+    fn wrap(table: SubstitutionTable) -> Self {
+        PyTable {
+            table: Arc::new(table),
+        }
+    }
+}
+
+#[pymethods]
+impl PyTable {
+    #[new]
+    fn new(mapping: HashMap<char, char>) -> PyResult<Self> {
+        SubstitutionTable::new(mapping)
+            .map(PyTable::wrap)
+            .map_err(domain_error)
+    }
+
+    #[staticmethod]
+    fn albhed() -> Self {
+        PyTable::wrap(SubstitutionTable::albhed().clone())
+    }
+
+    //前のグループのルールが優先される
+    #[staticmethod]
+    fn from_groups(groups: Vec<(String, HashMap<char, char>)>) -> PyResult<Self> {
+        let groups = groups
+            .into_iter()
+            .map(|(language, mapping)| Ok((parse_language(&language)?, mapping)))
+            .collect::<PyResult<Vec<_>>>()?;
+        SubstitutionTable::from_groups(groups)
+            .map(PyTable::wrap)
+            .map_err(domain_error)
+    }
+
+    fn restricted_to(&self, languages: Vec<String>) -> PyResult<Self> {
+        let languages = languages
+            .iter()
+            .map(|language| parse_language(language))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyTable::wrap(self.table.restricted_to(&languages)))
+    }
+
+    fn without_plaintext(&self, letters: &str) -> Self {
+        let letters: Vec<char> = letters.chars().collect();
+        PyTable::wrap(self.table.without_plaintext(&letters))
+    }
+
+    fn case_insensitive(&self) -> Self {
+        PyTable::wrap(self.table.case_insensitive())
+    }
+
+    #[getter]
+    fn languages(&self) -> Vec<&'static str> {
+        self.table
+            .languages()
+            .into_iter()
+            .map(language_name)
+            .collect()
+    }
+
+    fn pairs(&self) -> Vec<(char, char)> {
+        self.table.pairs()
+    }
+
+    fn encode_char(&self, c: char) -> char {
+        self.table.encode_char(c)
+    }
+
+    fn decode_char(&self, c: char) -> char {
+        self.table.decode_char(c)
+    }
+
+    fn encode(&self, py: Python<'_>, text: &str) -> String {
+        py.allow_threads(|| self.table.encode(text).into_owned())
+    }
+
+    fn decode(&self, py: Python<'_>, text: &str) -> String {
+        py.allow_threads(|| self.table.decode(text).into_owned())
+    }
+
+    //excluded は Python の文字列と同じ文字単位の [start, end)
+    fn encode_except(&self, text: &str, excluded: Vec<(usize, usize)>) -> PyResult<String> {
+        let excluded = ranges(&excluded)?;
+        let original = OriginalText::new(text).map_err(domain_error)?;
+        Ok(original
+            .encode_except(&self.table, &excluded)
+            .into_text()
+            .into_owned())
+    }
+
+    fn decode_except(&self, text: &str, excluded: Vec<(usize, usize)>) -> PyResult<String> {
+        let excluded = ranges(&excluded)?;
+        let albhed_text = AlBhedText::new(text).map_err(domain_error)?;
+        Ok(albhed_text
+            .decode_except(&self.table, &excluded)
+            .into_text()
+            .into_owned())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Table(languages={:?}, size={})",
+            self.languages(),
+            self.table.pairs().len()
+        )
+    }
+}
+
+fn ranges(excluded: &[(usize, usize)]) -> PyResult<Vec<Range<usize>>> {
+    excluded
+        .iter()
+        .map(|&(start, end)| {
+            if start > end {
+                return Err(PyValueError::new_err(format!(
+                    "Range start {start} is after its end {end}"
+                )));
+            }
+            Ok(start..end)
+        })
+        .collect()
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "TableInfo")]
+#[derive(Clone)]
+pub struct PyTableInfo {
+    name: String,
+    kind: &'static str,
+    description: String,
+    scripts: Vec<&'static str>,
+    size: usize,
+}
+
+impl From<&TableInfo> for PyTableInfo {
+    fn from(info: &TableInfo) -> Self {
+        PyTableInfo {
+            name: info.name().to_string(),
+            kind: kind_name(info.kind()),
+            description: info.description().to_string(),
+            scripts: info
+                .languages()
+                .iter()
+                .map(|language| script_name(language.script()))
+                .collect(),
+            size: info.size(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyTableInfo {
+    fn __repr__(&self) -> String {
+        format!("TableInfo(name={:?}, kind={:?})", self.name, self.kind)
+    }
+}
+
+#[pyclass(frozen, module = "albhed", name = "TableRegistry")]
+pub struct PyTableRegistry {
+    registry: Arc<TableRegistry>,
+}
+
+impl PyTableRegistry {
+    pub fn registry(&self) -> Arc<TableRegistry> {
+        self.registry.clone()
+    }
+}
+
+#[pymethods]
+impl PyTableRegistry {
+    #[new]
+    fn new() -> Self {
+        PyTableRegistry {
+            registry: Arc::new(TableRegistry::new()),
+        }
+    }
+
+    //名前順
+    fn tables(&self) -> Vec<PyTableInfo> {
+        self.registry.list().iter().map(PyTableInfo::from).collect()
+    }
+
+    fn info(&self, name: &str) -> Option<PyTableInfo> {
+        self.registry.info(name).as_ref().map(PyTableInfo::from)
+    }
+
+    fn get(&self, name: &str) -> Option<PyTable> {
+        self.registry.get(name).map(|table| PyTable { table })
+    }
+
+    #[pyo3(signature = (name, table, description = "Custom table"))]
+    fn register(&self, name: &str, table: PyTable, description: &str) -> PyResult<()> {
+        self.registry
+            .register(
+                name,
+                TableKind::Custom,
+                description,
+                table.table.as_ref().clone(),
+            )
+            .map(|_| ())
+            .map_err(domain_error)
+    }
+
+    //合言葉から表を作って登録し、その名前（ハッシュ）を返す
+    #[pyo3(signature = (passphrase, script = "both", allow_self_mapping = false))]
+    fn generate(
+        &self,
+        passphrase: &str,
+        script: &str,
+        allow_self_mapping: bool,
+    ) -> PyResult<String> {
+        let scripts = match script {
+            "both" => vec![Script::Latin, Script::Kana],
+            _ => vec![parse_script(script)?],
+        };
+        let table_port = TableInteractor::with_registry(self.registry.clone());
+        let generated = table_port
+            .generate(TableInputData::new(
+                passphrase,
+                &scripts,
+                allow_self_mapping,
+            ))
+            .map_err(usecase_error)?;
+        Ok(generated.get_hash().to_string())
+    }
+
+    //文字の種類ごとの (平文, アルベド語) の組
+    fn alphabet(&self, name: &str) -> PyResult<HashMap<&'static str, Vec<(char, char)>>> {
+        let cipher_port = CipherInteractor::with_registry(self.registry.clone());
+        let alphabet = cipher_port
+            .alphabet(CipherAlphabetInputData::new(name))
+            .map_err(usecase_error)?;
+        Ok(alphabet
+            .get_alphabets()
+            .iter()
+            .map(|(script, pairs)| (script_name(*script), pairs.clone()))
+            .collect())
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.registry.info(name).is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        let names: Vec<String> = self
+            .registry
+            .list()
+            .iter()
+            .map(|info| info.name().to_string())
+            .collect();
+        format!("TableRegistry({names:?})")
+    }
+}
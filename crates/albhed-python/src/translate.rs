@@ -0,0 +1,385 @@
+use albhed_usecase::domain::markup::TagHandling;
+use albhed_usecase::domain::pipeline::{processor, Phase, Pipeline, StageReport, TextChange};
+use albhed_usecase::domain::pronunciation::WordPronunciation;
+use albhed_usecase::domain::protection::{EntityKind, ProtectedSpan};
+use albhed_usecase::domain::ssml::SsmlOptions;
+use albhed_usecase::usecase::translate_usecase::{
+    CaseMode, Direction, TranslateInputData, TranslateInputPort, TranslateInteractor,
+    TranslateOutputData, TranslationOptions, TranslationWarning,
+};
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::PyDict;
+
+use crate::diagnostics::PyLintFinding;
+use crate::table::PyTableRegistry;
+use crate::{domain_error, kind_name, parse_script, script_name, unknown, usecase_error};
+
+//モジュールの encode / decode で使う、組み込みの表だけを持つ変換器
+static DEFAULT_TRANSLATOR: GILOnceCell<Py<PyTranslator>> = GILOnceCell::new();
+
+fn parse_entity_kind(name: &str) -> PyResult<EntityKind> {
+    match name {
+        "code" => Ok(EntityKind::Code),
+        "url" => Ok(EntityKind::Url),
+        "email" => Ok(EntityKind::Email),
+        "placeholder" => Ok(EntityKind::Placeholder),
+        "mention" => Ok(EntityKind::Mention),
+        "hashtag" => Ok(EntityKind::Hashtag),
+        _ => Err(unknown("entity kind", name)),
+    }
+}
+
+fn entity_kind_name(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Code => "code",
+        EntityKind::Url => "url",
+        EntityKind::Email => "email",
+        EntityKind::Placeholder => "placeholder",
+        EntityKind::Mention => "mention",
+        EntityKind::Hashtag => "hashtag",
+    }
+}
+
+#[pyclass(frozen, module = "albhed", name = "Translator")]
+pub struct PyTranslator {
+    interactor: TranslateInteractor,
+}
+
+#[pymethods]
+impl PyTranslator {
+    #[new]
+    #[pyo3(signature = (registry = None, parallel_threshold = None))]
+    fn new(
+        registry: Option<PyRef<'_, PyTableRegistry>>,
+        parallel_threshold: Option<usize>,
+    ) -> Self {
+        let mut interactor = match registry {
+            Some(registry) => TranslateInteractor::with_registry(registry.registry()),
+            None => TranslateInteractor::new(),
+        };
+        if let Some(bytes) = parallel_threshold {
+            interactor = interactor.with_parallel_threshold(bytes);
+        }
+        PyTranslator { interactor }
+    }
+
+    //指定は HTTP の POST /translate と同じ
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (
+        text,
+        direction = "encode",
+        *,
+        table = None,
+        case = None,
+        scripts = None,
+        protect = None,
+        markup = None,
+        lint = None,
+        primers = None,
+        pronounce = false,
+        ssml = None,
+        ssml_hints = false,
+        pipeline = None,
+        pre = Vec::new(),
+        post = Vec::new(),
+    ))]
+    fn translate(
+        &self,
+        py: Python<'_>,
+        text: &str,
+        direction: &str,
+        table: Option<&str>,
+        case: Option<&str>,
+        scripts: Option<Vec<String>>,
+        protect: Option<Vec<String>>,
+        markup: Option<&str>,
+        lint: Option<&str>,
+        primers: Option<&str>,
+        pronounce: bool,
+        ssml: Option<&str>,
+        ssml_hints: bool,
+        pipeline: Option<&str>,
+        pre: Vec<String>,
+        post: Vec<String>,
+    ) -> PyResult<PyTranslation> {
+        let direction = match direction {
+            "encode" => Direction::Encode,
+            "decode" => Direction::Decode,
+            _ => return Err(unknown("direction", direction)),
+        };
+
+        let mut options = TranslationOptions::builder();
+        if let Some(table) = table {
+            options = options.with_table(table);
+        }
+        if let Some(case) = case {
+            options = options.with_case(match case {
+                "exact" => CaseMode::Exact,
+                "preserve" => CaseMode::Preserve,
+                _ => return Err(unknown("case mode", case)),
+            });
+        }
+        if let Some(scripts) = scripts {
+            let scripts = scripts
+                .iter()
+                .map(|script| parse_script(script))
+                .collect::<PyResult<Vec<_>>>()?;
+            options = options.with_scripts(&scripts);
+        }
+        if let Some(protect) = protect {
+            let protections = protect
+                .iter()
+                .map(|kind| parse_entity_kind(kind))
+                .collect::<PyResult<Vec<_>>>()?;
+            options = options.with_protections(&protections);
+        }
+        if let Some(markup) = markup {
+            options = options.with_markup(match markup {
+                "strip" => TagHandling::Strip,
+                "keep" => TagHandling::Keep,
+                _ => return Err(unknown("markup handling", markup)),
+            });
+        }
+        if let Some(lint) = lint {
+            options = options.with_lint_mode(lint.parse().map_err(usecase_error)?);
+        }
+        if let Some(primers) = primers {
+            let letters: Vec<char> = primers.chars().collect();
+            options = options.with_primers(&letters);
+        }
+        if pronounce {
+            options = options.with_pronunciation();
+        }
+        if let Some(language) = ssml {
+            let mut ssml_options = SsmlOptions::new(language);
+            if ssml_hints {
+                ssml_options = ssml_options.with_hints();
+            }
+            options = options.with_ssml(ssml_options);
+        }
+        //profile の処理のあとに pre / post の処理を足す
+        if pipeline.is_some() || !pre.is_empty() || !post.is_empty() {
+            let mut stages = match pipeline {
+                Some(profile) => Pipeline::profile(profile).map_err(domain_error)?,
+                None => Pipeline::new(),
+            };
+            for name in &pre {
+                stages = stages.with_pre(processor(name).map_err(domain_error)?);
+            }
+            for name in &post {
+                stages = stages.with_post(processor(name).map_err(domain_error)?);
+            }
+            options = options.with_pipeline(stages);
+        }
+
+        let input_data = TranslateInputData::new(text, direction, options.build());
+        let translated = py
+            .allow_threads(|| self.interactor.translate(input_data))
+            .map_err(usecase_error)?;
+        Ok(PyTranslation::from(&translated))
+    }
+
+    #[pyo3(signature = (text, **options))]
+    fn encode<'py>(
+        slf: &Bound<'py, Self>,
+        text: &str,
+        options: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method("translate", (text, "encode"), options)
+    }
+
+    #[pyo3(signature = (text, **options))]
+    fn decode<'py>(
+        slf: &Bound<'py, Self>,
+        text: &str,
+        options: Option<&Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method("translate", (text, "decode"), options)
+    }
+}
+
+fn default_translator(py: Python<'_>) -> PyResult<&Bound<'_, PyTranslator>> {
+    DEFAULT_TRANSLATOR
+        .get_or_try_init(py, || Py::new(py, PyTranslator::new(None, None)))
+        .map(|translator| translator.bind(py))
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, **options))]
+pub fn encode<'py>(
+    py: Python<'py>,
+    text: &str,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    default_translator(py)?.call_method("translate", (text, "encode"), options)
+}
+
+#[pyfunction]
+#[pyo3(signature = (text, **options))]
+pub fn decode<'py>(
+    py: Python<'py>,
+    text: &str,
+    options: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    default_translator(py)?.call_method("translate", (text, "decode"), options)
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "Translation")]
+pub struct PyTranslation {
+    text: String,
+    direction: &'static str,
+    table: String,
+    kind: &'static str,
+    scripts: Vec<&'static str>,
+    lint: Vec<PyLintFinding>,
+    ignored_options: Vec<&'static str>,
+    protected: Option<Vec<PyProtectedSpan>>,
+    pronunciation: Option<Vec<PyPronunciation>>,
+    ssml: Option<String>,
+    stages: Vec<PyStageReport>,
+}
+
+impl From<&TranslateOutputData> for PyTranslation {
+    fn from(translated: &TranslateOutputData) -> Self {
+        let metadata = translated.get_metadata();
+        PyTranslation {
+            text: translated.get_text().to_string(),
+            direction: match translated.get_direction() {
+                Direction::Encode => "encode",
+                Direction::Decode => "decode",
+            },
+            table: metadata.get_table().to_string(),
+            kind: kind_name(metadata.get_kind()),
+            scripts: metadata
+                .get_scripts()
+                .iter()
+                .map(|&script| script_name(script))
+                .collect(),
+            lint: translated
+                .get_lint_findings()
+                .iter()
+                .map(PyLintFinding::from)
+                .collect(),
+            ignored_options: translated
+                .get_warnings()
+                .iter()
+                .filter_map(|warning| match warning {
+                    TranslationWarning::IgnoredOption(option) => Some(*option),
+                    TranslationWarning::SuspiciousCharacter(_) => None,
+                })
+                .collect(),
+            protected: translated
+                .get_protected()
+                .map(|spans| spans.iter().map(PyProtectedSpan::from).collect()),
+            pronunciation: translated
+                .get_pronunciation()
+                .map(|words| words.iter().map(PyPronunciation::from).collect()),
+            ssml: translated.get_ssml().map(str::to_string),
+            stages: metadata
+                .get_stage_reports()
+                .iter()
+                .map(PyStageReport::from)
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyTranslation {
+    fn __str__(&self) -> &str {
+        &self.text
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Translation(text={:?}, direction={:?}, table={:?})",
+            self.text, self.direction, self.table
+        )
+    }
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "ProtectedSpan")]
+#[derive(Clone)]
+pub struct PyProtectedSpan {
+    kind: &'static str,
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl From<&ProtectedSpan> for PyProtectedSpan {
+    fn from(span: &ProtectedSpan) -> Self {
+        PyProtectedSpan {
+            kind: entity_kind_name(span.kind()),
+            start: span.start(),
+            end: span.end(),
+            text: span.text().to_string(),
+        }
+    }
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "Pronunciation")]
+#[derive(Clone)]
+pub struct PyPronunciation {
+    start: usize,
+    end: usize,
+    word: String,
+    syllables: Vec<String>,
+    respelling: String,
+    ipa: String,
+}
+
+impl From<&WordPronunciation> for PyPronunciation {
+    fn from(word: &WordPronunciation) -> Self {
+        PyPronunciation {
+            start: word.start(),
+            end: word.end(),
+            word: word.word().to_string(),
+            syllables: word.syllables().to_vec(),
+            respelling: word.respelling().to_string(),
+            ipa: word.ipa().to_string(),
+        }
+    }
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "TextChange")]
+#[derive(Clone)]
+pub struct PyTextChange {
+    start: usize,
+    end: usize,
+    before: String,
+    after: String,
+}
+
+impl From<&TextChange> for PyTextChange {
+    fn from(change: &TextChange) -> Self {
+        PyTextChange {
+            start: change.start(),
+            end: change.end(),
+            before: change.before().to_string(),
+            after: change.after().to_string(),
+        }
+    }
+}
+
+#[pyclass(frozen, get_all, module = "albhed", name = "StageReport")]
+#[derive(Clone)]
+pub struct PyStageReport {
+    stage: String,
+    phase: &'static str,
+    changes: Vec<PyTextChange>,
+}
+
+impl From<&StageReport> for PyStageReport {
+    fn from(report: &StageReport) -> Self {
+        PyStageReport {
+            stage: report.stage().to_string(),
+            phase: match report.phase() {
+                Phase::Pre => "pre",
+                Phase::Post => "post",
+            },
+            changes: report.changes().iter().map(PyTextChange::from).collect(),
+        }
+    }
+}
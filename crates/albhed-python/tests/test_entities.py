@@ -0,0 +1,132 @@
+# Mirrors the unit tests in crates/albhed-core/src/entities.rs.
+import pytest
+
+import albhed
+from albhed import AlBhedError, Table
+
+
+def test_new_with_empty_text():
+    with pytest.raises(AlBhedError) as error:
+        Table.albhed().decode_except("", [])
+    assert error.value.code == "empty_text"
+
+
+def test_decode_jp():
+    decoded = albhed.decode("ギアンダメネ！ ラッラソ マッキンキノ！")
+    assert decoded.text == "じかんがねえ！ さっさと はっしんしろ！"
+
+
+def test_encode_jp():
+    encoded = albhed.encode("やっちまうか！？")
+    assert encoded.text == "タッヒヤフア！？"
+
+
+def test_substitution_table_rejects_duplicates():
+    with pytest.raises(AlBhedError) as error:
+        Table({"A": "X", "B": "X"})
+    assert error.value.code == "duplicate_mapping"
+
+
+def test_encode_with_custom_table():
+    table = Table({"A": "B", "B": "A"})
+    encoded = table.encode("ABC")
+    assert encoded == "BAC"
+    assert table.decode(encoded) == "ABC"
+
+
+def test_encode_except():
+    table = Table.albhed()
+    encoded = table.encode_except("HI @RIKKU ゆうな", [(3, 9), (10, 11)])
+    assert encoded == "RE @RIKKU ゆフハ"
+    assert table.decode_except(encoded, [(3, 9), (10, 11)]) == "HI @RIKKU ゆうな"
+
+
+def test_encode_except_rejects_inverted_ranges():
+    with pytest.raises(ValueError):
+        Table.albhed().encode_except("HELLO", [(4, 1)])
+    with pytest.raises(ValueError):
+        Table.albhed().decode_except("RAMMU", [(0, 2), (3, 2)])
+
+
+def test_encode_restricted_to_language():
+    original = "HELLO こんにちは"
+
+    japanese = Table.albhed().restricted_to(["japanese"])
+    assert japanese.encode(original) == "HELLO ヨンシヒマ"
+
+    english = Table.albhed().restricted_to(["english"])
+    assert english.encode(original) == "RAMMU こんにちは"
+
+
+def test_encode_without_plaintext():
+    primers = Table.albhed().without_plaintext("EL")
+    assert primers.encode("HELLO") == "RELLU"
+    assert primers.decode_char("A") == "A"
+    assert primers.decode_char("R") == "H"
+
+
+def test_encode_case_insensitive():
+    table = Table.albhed().case_insensitive()
+    encoded = table.encode("Hello, World")
+    assert encoded == "Rammu, Funmt"
+    assert table.decode(encoded) == "Hello, World"
+
+
+def test_unchanged_text_is_borrowed():
+    table = Table.albhed()
+    assert table.encode("123, 456!\r\n") == "123, 456!\r\n"
+    assert table.decode("👍 漢字") == "👍 漢字"
+    assert table.encode("12 AB") == "12 YP"
+    assert albhed.encode("...").text == "..."
+
+
+def test_lookup_prefers_earlier_groups():
+    table = Table.from_groups(
+        [
+            ("english", {"A": "ア", "ー": "B"}),
+            ("japanese", {"A": "C", "あ": "Z"}),
+        ]
+    )
+    assert table.encode("Aあー") == "アZB"
+    assert table.decode("アZBC") == "AあーA"
+    assert table.encode_char("\U0001F600") == "\U0001F600"
+
+
+# (input, Al Bhed) pairs
+GRAPHEME_CORPUS = [
+    # ZWJ emoji
+    ("👨\u200d👩\u200d👧", "👨\u200d👩\u200d👧"),
+    ("🏳\ufe0f\u200d🌈", "🏳\ufe0f\u200d🌈"),
+    # flags
+    ("🇯🇵🇺🇸", "🇯🇵🇺🇸"),
+    # keycaps
+    ("1\ufe0f\u20e3#\ufe0f\u20e3", "1\ufe0f\u20e3#\ufe0f\u20e3"),
+    ("A\u20e3", "A\u20e3"),
+    # skin tone modifiers
+    ("👍\U0001F3FD", "👍\U0001F3FD"),
+    ("👩\U0001F3FF\u200d💻", "👩\U0001F3FF\u200d💻"),
+    # variation selectors
+    ("A\ufe0f", "A\ufe0f"),
+    # combining diacritical marks
+    ("CAFE\u0301", "LYVA\u0301"),
+    ("N\u0303O\u0308\u0301", "H\u0303U\u0308\u0301"),
+    # mixed
+    ("HI 👋\U0001F3FB あ", "RE 👋\U0001F3FB ワ"),
+]
+
+
+@pytest.mark.parametrize("original, expected", GRAPHEME_CORPUS)
+def test_encode_grapheme_corpus(original, expected):
+    assert albhed.encode(original).text == expected
+
+
+@pytest.mark.parametrize("expected, encoded", GRAPHEME_CORPUS)
+def test_decode_grapheme_corpus(expected, encoded):
+    assert albhed.decode(encoded).text == expected
+
+
+def test_encode_voicing_marks():
+    encoded = albhed.encode("か\u3099ん\u3099は\u309a").text
+    # ん takes no dakuten, so the mark stays as it is
+    assert encoded == "ダん\u3099プ"
+    assert albhed.decode(encoded).text == "がん\u3099ぱ"
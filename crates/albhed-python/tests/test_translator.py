@@ -0,0 +1,68 @@
+import pytest
+
+import albhed
+from albhed import AlBhedError, Table, TableRegistry, Translator
+
+
+def test_translate_options():
+    translated = albhed.encode(
+        "Hello @rikku", case="preserve", protect=["mention"], pronounce=True
+    )
+    assert translated.text == "Rammu @rikku"
+    assert translated.direction == "encode"
+    assert (translated.table, translated.kind) == ("albhed", "builtin")
+    assert [span.text for span in translated.protected] == ["@rikku"]
+    assert translated.pronunciation[0].word == "Rammu"
+    assert str(translated) == "Rammu @rikku"
+
+
+def test_translate_scripts_and_primers():
+    assert albhed.encode("HI やあ", scripts=["kana"]).text == "HI タワ"
+    assert albhed.encode("HELLO", primers="EL").text == "RELLU"
+
+
+def test_translate_unknown_options():
+    with pytest.raises(AlBhedError) as error:
+        albhed.encode("HI", table="missing")
+    assert error.value.code == "unknown_table"
+    with pytest.raises(ValueError):
+        albhed.encode("HI", case="upper")
+    with pytest.raises(ValueError):
+        Translator().translate("HI", "sideways")
+
+
+def test_registry():
+    registry = TableRegistry()
+    name = registry.generate("zebras", script="latin", allow_self_mapping=True)
+    assert name in registry
+    assert registry.info(name).kind == "keyed"
+    assert registry.alphabet(name)["latin"][0] == ("A", "Z")
+
+    # keyed tables are only reachable by their hash
+    assert name not in [info.name for info in registry.tables()]
+
+    registry.register("swap", Table({"A": "B", "B": "A"}))
+    assert [info.name for info in registry.tables()] == ["albhed", "swap"]
+    assert registry.info("swap").kind == "custom"
+    for taken in ["albhed", "swap", name]:
+        with pytest.raises(AlBhedError) as error:
+            registry.register(taken, Table({"A": "B", "B": "A"}))
+        assert error.value.code == "duplicate_table_name"
+    assert registry.get("albhed").encode("HI") == "RE"
+
+    translator = Translator(registry)
+    translated = translator.encode("ABC", table="swap")
+    assert (translated.text, translated.kind) == ("BAC", "custom")
+    assert translator.decode("BAC", table="swap").text == "ABC"
+
+
+def test_diagnostics():
+    findings = albhed.lint("A\u200bB")
+    assert [(f.kind, f.code_point) for f in findings] == [("zero_width", "U+200B")]
+
+    translated = albhed.encode("A\u200bB", lint="report")
+    assert [f.kind for f in translated.lint] == ["zero_width"]
+
+    misspellings = albhed.spellcheck("RAMMI")
+    assert misspellings[0].text == "RAMMI"
+    assert "HELLO" in [s.decoded for s in misspellings[0].suggestions]
@@ -0,0 +1,18 @@
+pub use albhed_core::entities;
+
+pub mod cipher_plugin;
+pub mod error;
+pub mod keyed_table;
+pub mod lint;
+pub mod markup;
+pub mod name_generator;
+pub mod parallel;
+pub mod pipeline;
+pub mod placeholder;
+pub mod pronunciation;
+pub mod protection;
+pub mod random;
+pub mod spellcheck;
+pub mod ssml;
+pub mod table_registry;
+pub mod vocabulary;
@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+use crate::domain::error::DomainError;
+
+//表では書けない変換（前後の文字で変わる規則など）をする暗号
+pub trait CipherPlugin: Send + Sync {
+    fn encode(&self, text: &str) -> Result<String, DomainError>;
+    fn decode(&self, text: &str) -> Result<String, DomainError>;
+}
+
+//指定した範囲（文字位置）はそのまま残し、それ以外はひと続きごとに変換する
+//convert は区切られたひと続きごとに別々に呼ぶ
+pub fn convert_except(
+    text: &str,
+    excluded: &[Range<usize>],
+    convert: impl Fn(&str) -> Result<String, DomainError>,
+) -> Result<String, DomainError> {
+    //範囲を始まりの順に並べ、今の位置より前に終わるものを読み飛ばしながら進む
+    let mut excluded = excluded.to_vec();
+    excluded.sort_by_key(|range| range.start);
+    let mut next = 0;
+    let mut converted = String::with_capacity(text.len());
+    let mut segment = String::new();
+    for (i, c) in text.chars().enumerate() {
+        while excluded.get(next).is_some_and(|range| range.end <= i) {
+            next += 1;
+        }
+        if excluded.get(next).is_some_and(|range| range.start <= i) {
+            if !segment.is_empty() {
+                converted.push_str(&convert(&segment)?);
+                segment.clear();
+            }
+            converted.push(c);
+        } else {
+            segment.push(c);
+        }
+    }
+    if !segment.is_empty() {
+        converted.push_str(&convert(&segment)?);
+    }
+    Ok(converted)
+}
+
+//ほかのモジュールのテストでも使うプラグイン
+#[cfg(test)]
+pub(crate) mod test_plugins {
+    use super::CipherPlugin;
+    use crate::domain::error::DomainError;
+
+    //前後の文字で結果が変わる暗号の例として、入力を逆順にする
+    pub(crate) struct Reverse;
+
+    impl CipherPlugin for Reverse {
+        fn encode(&self, text: &str) -> Result<String, DomainError> {
+            Ok(text.chars().rev().collect())
+        }
+
+        fn decode(&self, text: &str) -> Result<String, DomainError> {
+            self.encode(text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_except() {
+        let converted = convert_except("ab cd ef", &[2..3, 5..6], |segment| {
+            Ok(format!("[{}]", segment.to_uppercase()))
+        });
+
+        assert_eq!(converted.unwrap(), "[AB] [CD] [EF]");
+    }
+
+    #[test]
+    fn test_convert_except_per_segment() {
+        let reverse = |segment: &str| Ok(segment.chars().rev().collect());
+        assert_eq!(convert_except("ab cd", &[], reverse).unwrap(), "dc ba");
+        assert_eq!(
+            convert_except("ab cd ef", &[2..3, 5..6], reverse).unwrap(),
+            "ba dc fe"
+        );
+    }
+
+    #[test]
+    fn test_convert_except_unsorted_ranges() {
+        let upper = |segment: &str| Ok(segment.to_uppercase());
+        let converted = convert_except("ab cd ef gh", &[6..8, 0..4, 1..2, 9..9], upper);
+        assert_eq!(converted.unwrap(), "ab cD ef GH");
+    }
+
+    #[test]
+    fn test_convert_except_many_ranges_in_linear_time() {
+        let text = "ab ".repeat(100_000);
+        let excluded: Vec<Range<usize>> = (0..100_000).map(|k| k * 3 + 2..k * 3 + 3).collect();
+        let started = std::time::Instant::now();
+        let converted = convert_except(&text, &excluded, |segment| Ok(segment.to_uppercase()));
+        assert_eq!(converted.unwrap(), "AB ".repeat(100_000));
+        assert!(started.elapsed().as_secs() < 2);
+    }
+
+    #[test]
+    fn test_convert_except_error() {
+        let converted = convert_except("ab", &[], |_| {
+            Err(DomainError::PluginRejected {
+                plugin: "test".to_string(),
+            })
+        });
+
+        assert_eq!(converted.unwrap_err().code(), "plugin_rejected");
+    }
+}
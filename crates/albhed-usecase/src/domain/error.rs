@@ -0,0 +1,106 @@
+use thiserror::Error;
+
+//文字列の型と換字表そのものの誤り
+pub use albhed_core::error::DomainError as EntityError;
+
+type Source = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Error)]
+pub enum DomainError {
+    #[error(transparent)]
+    Entity(#[from] EntityError),
+    #[error("Table {0} is already registered")]
+    DuplicateTableName(String),
+    #[error("Empty passphrase")]
+    EmptyPassphrase,
+    #[error("No script selected")]
+    NoScriptSelected,
+    #[error("No table without self-mapping for this passphrase")]
+    NoDerangement,
+    #[error("Unexpected closing tag {tag} at {position}")]
+    UnexpectedClosingTag { tag: &'static str, position: usize },
+    #[error("Nested tag {tag} at {position} inside tag opened at {outer}")]
+    NestedTag {
+        tag: &'static str,
+        position: usize,
+        outer: usize,
+    },
+    #[error("Tag {tag} at {position} closed by {closing} at {closing_position}")]
+    MismatchedTag {
+        tag: &'static str,
+        position: usize,
+        closing: &'static str,
+        closing_position: usize,
+    },
+    #[error("Unclosed tag {tag} at {position}")]
+    UnclosedTag { tag: &'static str, position: usize },
+    #[error("Unknown pipeline profile: {0}")]
+    UnknownPipelineProfile(String),
+    #[error("Unknown pipeline stage: {0}")]
+    UnknownPipelineStage(String),
+    #[error("Pipeline stage {stage} failed")]
+    StageFailed {
+        stage: String,
+        #[source]
+        source: Source,
+    },
+    #[error("Syllable count must be between {min} and {max}")]
+    InvalidSyllableCount { min: usize, max: usize },
+    #[error("Could not generate a name with these options")]
+    NameGenerationExhausted,
+    #[error("Plugin {plugin} rejected the input")]
+    PluginRejected { plugin: String },
+    #[error("Plugin {plugin} failed")]
+    PluginFailed {
+        plugin: String,
+        #[source]
+        source: Source,
+    },
+}
+
+impl DomainError {
+    //APIの利用者に返す、変わらない識別子
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::Entity(error) => error.code(),
+            DomainError::DuplicateTableName(_) => "duplicate_table_name",
+            DomainError::EmptyPassphrase => "empty_passphrase",
+            DomainError::NoScriptSelected => "no_script_selected",
+            DomainError::NoDerangement => "no_derangement",
+            DomainError::UnexpectedClosingTag { .. }
+            | DomainError::NestedTag { .. }
+            | DomainError::MismatchedTag { .. }
+            | DomainError::UnclosedTag { .. } => "invalid_markup",
+            DomainError::UnknownPipelineProfile(_) => "unknown_pipeline_profile",
+            DomainError::UnknownPipelineStage(_) => "unknown_pipeline_stage",
+            DomainError::StageFailed { .. } => "pipeline_stage_failed",
+            DomainError::InvalidSyllableCount { .. } => "invalid_syllable_count",
+            DomainError::NameGenerationExhausted => "name_generation_exhausted",
+            DomainError::PluginRejected { .. } => "plugin_rejected",
+            DomainError::PluginFailed { .. } => "plugin_failed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn test_code_and_source() {
+        let error = DomainError::PluginFailed {
+            plugin: "rot13".to_string(),
+            source: "out of fuel".into(),
+        };
+
+        assert_eq!(error.code(), "plugin_failed");
+        assert_eq!(error.to_string(), "Plugin rot13 failed");
+        assert_eq!(error.source().unwrap().to_string(), "out of fuel");
+
+        let error = DomainError::from(EntityError::EmptyText);
+        assert_eq!(error.code(), "empty_text");
+        assert_eq!(error.to_string(), "Empty String");
+    }
+}
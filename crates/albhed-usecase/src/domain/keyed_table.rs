@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::domain::entities::{Script, SubstitutionTable};
+use crate::domain::error::DomainError;
+
+const LATIN_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const KANA_ALPHABET: &str = "あいうえおかきくけこさしすせそたちつてとなにぬねのはひふへほまみむめもやゆよらりるれろわをんがぎぐげござじずぜぞだぢづでどばびぶべぼぱぴぷぺぽぁぃぅぇぉゃゅょっ";
+
+//ひらがなとカタカナの文字コードの差
+const KATAKANA_OFFSET: u32 = 0x60;
+//ほかの用途のハッシュと同じ値にならないよう、用途ごとに頭に付ける
+const KEYWORD_DOMAIN: &[u8] = b"albhed-translator/keyed-table/keyword\0";
+const IDENTITY_DOMAIN: &[u8] = b"albhed-translator/keyed-table/identity\0";
+
+pub struct KeyedTable {
+    hash: String,
+    table: SubstitutionTable,
+}
+
+impl KeyedTable {
+    //合言葉から鍵付きアルファベット方式で換字表を作る
+    //鍵となる文字は合言葉に含まれる対象文字、なければ合言葉のハッシュから決める
+    //secret は識別子を求めるときのサーバーの秘密鍵（なければ空）
+    pub fn generate(
+        passphrase: &str,
+        scripts: &[Script],
+        allow_self_mapping: bool,
+        secret: &[u8],
+    ) -> Result<Self, DomainError> {
+        if passphrase.is_empty() {
+            return Err(DomainError::EmptyPassphrase);
+        }
+        if scripts.is_empty() {
+            return Err(DomainError::NoScriptSelected);
+        }
+
+        let digest = Sha256::new()
+            .chain_update(KEYWORD_DOMAIN)
+            .chain_update(passphrase.as_bytes())
+            .finalize();
+        let mut mapping = HashMap::new();
+        for script in [Script::Latin, Script::Kana] {
+            if !scripts.contains(&script) {
+                continue;
+            }
+            let alphabet: Vec<char> = match script {
+                Script::Latin => LATIN_ALPHABET.chars().collect(),
+                Script::Kana => KANA_ALPHABET.chars().collect(),
+            };
+            let keyed = keyed_alphabet(&alphabet, &keyword(passphrase, script, &alphabet, &digest));
+            let shift = if allow_self_mapping {
+                0
+            } else {
+                derangement_shift(&alphabet, &keyed).ok_or(DomainError::NoDerangement)?
+            };
+
+            for (i, &plain) in alphabet.iter().enumerate() {
+                let cipher = keyed[(i + shift) % keyed.len()];
+                let cipher = match script {
+                    Script::Latin => cipher,
+                    Script::Kana => to_katakana(cipher),
+                };
+                mapping.insert(plain, cipher);
+            }
+        }
+
+        //指定の順番や重複で同じ表に別の識別子が付かないよう、決まった順で数える
+        let mut identity = format!("{}\0", passphrase);
+        for script in [Script::Latin, Script::Kana]
+            .iter()
+            .filter(|script| scripts.contains(script))
+        {
+            identity.push_str(&format!("{:?}\0", script));
+        }
+        identity.push_str(if allow_self_mapping { "1" } else { "0" });
+
+        //識別子は秘密ではない。秘密鍵がなければ、弱い合言葉は識別子から総当たりで当てられる
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+        mac.update(IDENTITY_DOMAIN);
+        mac.update(identity.as_bytes());
+        let hash = mac.finalize().into_bytes()[..16]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        Ok(KeyedTable {
+            hash,
+            table: SubstitutionTable::new(mapping)?,
+        })
+    }
+
+    //合言葉と生成オプションから求めた表の識別子
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    pub fn table(&self) -> &SubstitutionTable {
+        &self.table
+    }
+
+    pub fn into_table(self) -> SubstitutionTable {
+        self.table
+    }
+}
+
+fn keyword(passphrase: &str, script: Script, alphabet: &[char], digest: &[u8]) -> Vec<char> {
+    let letters: Vec<char> = passphrase
+        .chars()
+        .map(|c| match script {
+            Script::Latin => c.to_ascii_uppercase(),
+            Script::Kana => to_hiragana(c),
+        })
+        .filter(|c| alphabet.contains(c))
+        .collect();
+    if !letters.is_empty() {
+        return letters;
+    }
+
+    digest
+        .iter()
+        .map(|&b| alphabet[b as usize % alphabet.len()])
+        .collect()
+}
+
+//鍵の文字（重複は除く）の後に残りの文字を元の順で並べる
+fn keyed_alphabet(alphabet: &[char], keyword: &[char]) -> Vec<char> {
+    let mut keyed: Vec<char> = Vec::with_capacity(alphabet.len());
+    for &c in keyword.iter().chain(alphabet.iter()) {
+        if !keyed.contains(&c) {
+            keyed.push(c);
+        }
+    }
+    keyed
+}
+
+//どの文字も自分自身に対応しないずらし幅を探す
+fn derangement_shift(alphabet: &[char], keyed: &[char]) -> Option<usize> {
+    (0..keyed.len()).find(|&shift| {
+        alphabet
+            .iter()
+            .enumerate()
+            .all(|(i, &c)| keyed[(i + shift) % keyed.len()] != c)
+    })
+}
+
+fn to_katakana(c: char) -> char {
+    char::from_u32(c as u32 + KATAKANA_OFFSET).unwrap_or(c)
+}
+
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - KATAKANA_OFFSET).unwrap_or(c),
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::OriginalText;
+
+    #[test]
+    fn test_generate_latin_keyed_alphabet() {
+        let keyed = KeyedTable::generate("zebras", &[Script::Latin], true, b"").unwrap();
+        let encoded = OriginalText::new("ABCDEZ")
+            .unwrap()
+            .encode_with(keyed.table());
+        // ZEBRASCDFGHIJKLMNOPQTUVWXY
+        assert_eq!(encoded.text(), "ZEBRAY");
+        assert_eq!(encoded.decode_with(keyed.table()).text(), "ABCDEZ");
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let first =
+            KeyedTable::generate("秘密の合言葉", &[Script::Latin, Script::Kana], false, b"");
+        let second =
+            KeyedTable::generate("秘密の合言葉", &[Script::Latin, Script::Kana], false, b"");
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_eq!(first.hash(), second.hash());
+        assert_eq!(first.table().pairs(), second.table().pairs());
+
+        let reordered = KeyedTable::generate(
+            "秘密の合言葉",
+            &[Script::Kana, Script::Latin, Script::Kana],
+            false,
+            b"",
+        )
+        .unwrap();
+        assert_eq!(reordered.hash(), first.hash());
+    }
+
+    #[test]
+    fn test_generate_without_self_mapping() {
+        let keyed =
+            KeyedTable::generate("zebras", &[Script::Latin, Script::Kana], false, b"").unwrap();
+        assert!(keyed
+            .table()
+            .pairs()
+            .iter()
+            .all(|&(plain, cipher)| plain != cipher && to_hiragana(cipher) != plain));
+    }
+
+    #[test]
+    fn test_generate_only_selected_script() {
+        let keyed = KeyedTable::generate("あいことば", &[Script::Kana], true, b"").unwrap();
+        let encoded = OriginalText::new("ABC あい")
+            .unwrap()
+            .encode_with(keyed.table());
+        assert_eq!(encoded.text(), "ABC アイ");
+    }
+
+    #[test]
+    fn test_generate_with_secret() {
+        let plain = KeyedTable::generate("zebras", &[Script::Latin], false, b"").unwrap();
+        let keyed = KeyedTable::generate("zebras", &[Script::Latin], false, b"secret").unwrap();
+        //秘密鍵で変わるのは識別子だけ
+        assert_ne!(plain.hash(), keyed.hash());
+        assert_eq!(plain.table().pairs(), keyed.table().pairs());
+        assert_eq!(keyed.hash().len(), 32);
+    }
+
+    #[test]
+    fn test_generate_empty_passphrase() {
+        assert!(KeyedTable::generate("", &[Script::Latin], true, b"").is_err());
+    }
+}
@@ -0,0 +1,376 @@
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const ZERO_WIDTH_NON_JOINER: char = '\u{200C}';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintKind {
+    BidiControl,
+    ZeroWidth,
+    Homoglyph,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintFinding {
+    kind: LintKind,
+    position: usize,
+    character: char,
+    replacement: Option<char>,
+}
+
+impl LintFinding {
+    pub fn kind(&self) -> LintKind {
+        self.kind
+    }
+
+    //入力テキスト中の文字（char）単位の位置
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn character(&self) -> char {
+        self.character
+    }
+
+    //ホモグリフのときだけ、置き換え先のラテン文字
+    pub fn replacement(&self) -> Option<char> {
+        self.replacement
+    }
+}
+
+//双方向テキストの制御文字、ゼロ幅文字、ラテン文字に似たキリル・ギリシャ文字を探す
+//似た文字は、ラテン文字と混ざった単語の中にあるものだけを数える（ロシア語やギリシャ語の文はそのまま）
+pub fn find_suspicious(text: &str) -> Vec<LintFinding> {
+    let chars: Vec<char> = text.chars().collect();
+    let mixed = mixed_with_latin(&chars);
+    let mut findings = Vec::new();
+    for (position, &c) in chars.iter().enumerate() {
+        let replacement = homoglyph(c).filter(|_| mixed[position]);
+        let kind = if is_bidi_control(c) {
+            Some(LintKind::BidiControl)
+        } else if is_zero_width(c) && !is_expected_joiner(&chars, position) {
+            Some(LintKind::ZeroWidth)
+        } else if replacement.is_some() {
+            Some(LintKind::Homoglyph)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            findings.push(LintFinding {
+                kind,
+                position,
+                character: c,
+                replacement,
+            });
+        }
+    }
+    findings
+}
+
+//各文字について、その文字を含む単語（英数字の連なり）にラテン文字があるか
+fn mixed_with_latin(chars: &[char]) -> Vec<bool> {
+    let mut mixed = vec![false; chars.len()];
+    let mut start = 0;
+    while start < chars.len() {
+        let len = chars[start..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric())
+            .count();
+        if len == 0 {
+            start += 1;
+            continue;
+        }
+        let word = start..start + len;
+        if chars[word.clone()].iter().any(|&c| is_latin_letter(c)) {
+            mixed[word.clone()].fill(true);
+        }
+        start = word.end;
+    }
+    mixed
+}
+
+fn is_latin_letter(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '\u{C0}'..='\u{24F}' if c.is_alphabetic())
+}
+
+//絵文字のZWJシーケンスのZWJと、ペルシャ語やインドの文字で使うZWNJは正当なものとして扱う
+fn is_expected_joiner(chars: &[char], position: usize) -> bool {
+    match chars[position] {
+        ZERO_WIDTH_JOINER => joins_pictographs(chars, position),
+        ZERO_WIDTH_NON_JOINER => {
+            let before = position.checked_sub(1).map(|k| chars[k]);
+            let after = chars.get(position + 1).copied();
+            [before, after].into_iter().flatten().any(uses_non_joiner)
+        }
+        _ => false,
+    }
+}
+
+//ZWNJで字形の続け方を変えるアラビア文字（ペルシャ語など）とインド系の文字
+fn uses_non_joiner(c: char) -> bool {
+    matches!(c,
+        '\u{0600}'..='\u{06FF}'
+        | '\u{0750}'..='\u{077F}'
+        | '\u{08A0}'..='\u{08FF}'
+        | '\u{0900}'..='\u{0DFF}'
+        | '\u{FB50}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}')
+}
+
+//ZWJの前後が Extended_Pictographic か。前の絵文字には異体字セレクタや肌の色の修飾子が付いていてもよい
+fn joins_pictographs(chars: &[char], position: usize) -> bool {
+    let before = chars[..position]
+        .iter()
+        .rev()
+        .find(|&&c| !is_emoji_modifier(c));
+    let after = chars.get(position + 1);
+    before.is_some_and(|&c| is_extended_pictographic(c))
+        && after.is_some_and(|&c| is_extended_pictographic(c))
+}
+
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(c, '\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}')
+}
+
+//Unicode の Extended_Pictographic
+fn is_extended_pictographic(c: char) -> bool {
+    matches!(c,
+        '\u{A9}'
+        | '\u{AE}'
+        | '\u{203C}'
+        | '\u{2049}'
+        | '\u{2122}'
+        | '\u{2139}'
+        | '\u{2194}'..='\u{2199}'
+        | '\u{21A9}'..='\u{21AA}'
+        | '\u{231A}'..='\u{231B}'
+        | '\u{2328}'
+        | '\u{2388}'
+        | '\u{23CF}'
+        | '\u{23E9}'..='\u{23F3}'
+        | '\u{23F8}'..='\u{23FA}'
+        | '\u{24C2}'
+        | '\u{25AA}'..='\u{25AB}'
+        | '\u{25B6}'
+        | '\u{25C0}'
+        | '\u{25FB}'..='\u{25FE}'
+        | '\u{2600}'..='\u{2605}'
+        | '\u{2607}'..='\u{2612}'
+        | '\u{2614}'..='\u{2685}'
+        | '\u{2690}'..='\u{2705}'
+        | '\u{2708}'..='\u{2712}'
+        | '\u{2714}'
+        | '\u{2716}'
+        | '\u{271D}'
+        | '\u{2721}'
+        | '\u{2728}'
+        | '\u{2733}'..='\u{2734}'
+        | '\u{2744}'
+        | '\u{2747}'
+        | '\u{274C}'
+        | '\u{274E}'
+        | '\u{2753}'..='\u{2755}'
+        | '\u{2757}'
+        | '\u{2763}'..='\u{2767}'
+        | '\u{2795}'..='\u{2797}'
+        | '\u{27A1}'
+        | '\u{27B0}'
+        | '\u{27BF}'
+        | '\u{2934}'..='\u{2935}'
+        | '\u{2B05}'..='\u{2B07}'
+        | '\u{2B1B}'..='\u{2B1C}'
+        | '\u{2B50}'
+        | '\u{2B55}'
+        | '\u{3030}'
+        | '\u{303D}'
+        | '\u{3297}'
+        | '\u{3299}'
+        | '\u{1F000}'..='\u{1F0FF}'
+        | '\u{1F10D}'..='\u{1F10F}'
+        | '\u{1F12F}'
+        | '\u{1F16C}'..='\u{1F171}'
+        | '\u{1F17E}'..='\u{1F17F}'
+        | '\u{1F18E}'
+        | '\u{1F191}'..='\u{1F19A}'
+        | '\u{1F1AD}'..='\u{1F1E5}'
+        | '\u{1F201}'..='\u{1F20F}'
+        | '\u{1F21A}'
+        | '\u{1F22F}'
+        | '\u{1F232}'..='\u{1F23A}'
+        | '\u{1F23C}'..='\u{1F23F}'
+        | '\u{1F249}'..='\u{1F3FA}'
+        | '\u{1F400}'..='\u{1F53D}'
+        | '\u{1F546}'..='\u{1F64F}'
+        | '\u{1F680}'..='\u{1F6FF}'
+        | '\u{1F774}'..='\u{1F77F}'
+        | '\u{1F7D5}'..='\u{1F7FF}'
+        | '\u{1F80C}'..='\u{1F80F}'
+        | '\u{1F848}'..='\u{1F84F}'
+        | '\u{1F85A}'..='\u{1F85F}'
+        | '\u{1F888}'..='\u{1F88F}'
+        | '\u{1F8AE}'..='\u{1F8FF}'
+        | '\u{1F90C}'..='\u{1F93A}'
+        | '\u{1F93C}'..='\u{1F945}'
+        | '\u{1F947}'..='\u{1FAFF}'
+        | '\u{1FC00}'..='\u{1FFFD}')
+}
+
+//ラテン文字と混ざった単語の中の似た文字だけをラテン文字に置き換える
+pub fn normalize_homoglyphs(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mixed = mixed_with_latin(&chars);
+    chars
+        .iter()
+        .zip(mixed)
+        .map(|(&c, mixed)| homoglyph(c).filter(|_| mixed).unwrap_or(c))
+        .collect()
+}
+
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{180E}'
+            | '\u{200B}'
+            | ZERO_WIDTH_NON_JOINER
+            | ZERO_WIDTH_JOINER
+            | '\u{2060}'
+            | '\u{FEFF}'
+    )
+}
+
+fn homoglyph(c: char) -> Option<char> {
+    let latin = match c {
+        //キリル文字
+        'А' => 'A',
+        'В' => 'B',
+        'Е' => 'E',
+        'К' => 'K',
+        'М' => 'M',
+        'Н' => 'H',
+        'О' => 'O',
+        'Р' => 'P',
+        'С' => 'C',
+        'Т' => 'T',
+        'У' => 'Y',
+        'Х' => 'X',
+        'Ѕ' => 'S',
+        'І' => 'I',
+        'Ј' => 'J',
+        'Ԛ' => 'Q',
+        'Ԝ' => 'W',
+        'а' => 'a',
+        'е' => 'e',
+        'о' => 'o',
+        'р' => 'p',
+        'с' => 'c',
+        'у' => 'y',
+        'х' => 'x',
+        'ѕ' => 's',
+        'і' => 'i',
+        'ј' => 'j',
+        'ԁ' => 'd',
+        'һ' => 'h',
+        'ԛ' => 'q',
+        'ԝ' => 'w',
+        //ギリシャ文字
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Υ' => 'Y',
+        'Χ' => 'X',
+        'ο' => 'o',
+        'ν' => 'v',
+        _ => return None,
+    };
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_suspicious() {
+        //キリル文字のА、ゼロ幅スペース、RLO
+        let findings = find_suspicious("\u{0410}B\u{200B}C\u{202E}D");
+        let summary: Vec<(LintKind, usize, Option<char>)> = findings
+            .iter()
+            .map(|f| (f.kind(), f.position(), f.replacement()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (LintKind::Homoglyph, 0, Some('A')),
+                (LintKind::ZeroWidth, 2, None),
+                (LintKind::BidiControl, 4, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_suspicious_allows_emoji_zwj() {
+        assert!(find_suspicious("👨\u{200D}👩\u{200D}👧 HI").is_empty());
+        assert!(find_suspicious("🏳\u{FE0F}\u{200D}🌈").is_empty());
+        assert!(find_suspicious("👩\u{1F3FF}\u{200D}💻").is_empty());
+        assert_eq!(find_suspicious("H\u{200D}I").len(), 1);
+    }
+
+    #[test]
+    fn test_find_suspicious_zwj_after_punctuation() {
+        for text in [
+            "!\u{200D}HI",
+            "-\u{200D}HI",
+            "HI!\u{200D}👍",
+            "👍\u{200D}!",
+            "👍\u{200D}",
+        ] {
+            let findings = find_suspicious(text);
+            assert_eq!(findings.len(), 1, "{:?}", text);
+            assert_eq!(findings[0].kind(), LintKind::ZeroWidth);
+            assert_eq!(findings[0].character(), '\u{200D}');
+        }
+    }
+
+    #[test]
+    fn test_find_suspicious_skips_cyrillic_and_greek_words() {
+        assert!(find_suspicious("Привет, как дела? Скоро отпуск.").is_empty());
+        assert!(find_suspicious("Καλημέρα κόσμε, ΟΚ").is_empty());
+
+        //ラテン文字と混ざった単語の中だけを数える
+        let findings = find_suspicious("рaypal и Paypаl");
+        let positions: Vec<usize> = findings.iter().map(|f| f.position()).collect();
+        assert_eq!(positions, vec![0, 13]);
+    }
+
+    #[test]
+    fn test_find_suspicious_allows_zwnj_in_persian_and_indic() {
+        //می‌خواهم（ペルシャ語）、क्‌ष（デーヴァナーガリー）
+        assert!(find_suspicious("می\u{200C}خواهم").is_empty());
+        assert!(find_suspicious("क्\u{200C}ष").is_empty());
+        assert_eq!(find_suspicious("A\u{200C}B").len(), 1);
+        assert_eq!(find_suspicious("\u{200C}").len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_homoglyphs() {
+        assert_eq!(
+            normalize_homoglyphs("\u{0410}L\u{0392}\u{0397}ED"),
+            "ALBHED"
+        );
+        assert_eq!(normalize_homoglyphs("Скоро рaypal"), "Скоро paypal");
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::Range;
+
+use crate::domain::error::DomainError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagHandling {
+    Strip,
+    Keep,
+}
+
+//(開きタグ, 閉じタグ)
+const TAGS: [(&str, &str); 2] = [("<albhed>", "</albhed>"), ("[ab]", "[/ab]")];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TaggedSegment {
+    open: Range<usize>,
+    content: Range<usize>,
+    close: Range<usize>,
+}
+
+//<albhed>…</albhed> や [ab]…[/ab] で囲まれた部分だけを変換対象にする
+pub struct Markup {
+    chars: Vec<char>,
+    segments: Vec<TaggedSegment>,
+}
+
+impl Markup {
+    pub fn parse(text: &str) -> Result<Self, DomainError> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut segments = Vec::new();
+        //(開きタグの種類, 開きタグの範囲)
+        let mut open: Option<(usize, Range<usize>)> = None;
+        let mut i = 0;
+        while i < chars.len() {
+            let Some((tag, is_close, len)) = match_tag(&chars, i) else {
+                i += 1;
+                continue;
+            };
+            let range = i..i + len;
+            match (&open, is_close) {
+                (None, false) => open = Some((tag, range.clone())),
+                (None, true) => {
+                    return Err(DomainError::UnexpectedClosingTag {
+                        tag: TAGS[tag].1,
+                        position: range.start,
+                    })
+                }
+                (Some((_, outer)), false) => {
+                    return Err(DomainError::NestedTag {
+                        tag: TAGS[tag].0,
+                        position: range.start,
+                        outer: outer.start,
+                    })
+                }
+                (Some((open_tag, outer)), true) if *open_tag != tag => {
+                    return Err(DomainError::MismatchedTag {
+                        tag: TAGS[*open_tag].0,
+                        position: outer.start,
+                        closing: TAGS[tag].1,
+                        closing_position: range.start,
+                    })
+                }
+                (Some((_, outer)), true) => {
+                    segments.push(TaggedSegment {
+                        open: outer.clone(),
+                        content: outer.end..range.start,
+                        close: range.clone(),
+                    });
+                    open = None;
+                }
+            }
+            i = range.end;
+        }
+
+        if let Some((tag, range)) = open {
+            return Err(DomainError::UnclosedTag {
+                tag: TAGS[tag].0,
+                position: range.start,
+            });
+        }
+
+        Ok(Markup { chars, segments })
+    }
+
+    pub fn text(&self, handling: TagHandling) -> String {
+        match handling {
+            TagHandling::Keep => self.chars.iter().collect(),
+            TagHandling::Strip => {
+                //segments は位置の順に並んでいる
+                let mut text = String::with_capacity(self.chars.len());
+                let mut last = 0;
+                for segment in &self.segments {
+                    text.extend(&self.chars[last..segment.open.start]);
+                    text.extend(&self.chars[segment.content.clone()]);
+                    last = segment.close.end;
+                }
+                text.extend(&self.chars[last..]);
+                text
+            }
+        }
+    }
+
+    //text(handling) の中で変換しない範囲（文字位置）
+    pub fn excluded(&self, handling: TagHandling) -> Vec<Range<usize>> {
+        let strip = handling == TagHandling::Strip;
+        let mut excluded = Vec::new();
+        let mut last = 0;
+        let mut removed = 0;
+        for segment in &self.segments {
+            if strip {
+                removed += segment.open.len();
+            }
+            excluded.push(last..segment.content.start - removed);
+            last = segment.content.end - removed;
+            if strip {
+                removed += segment.close.len();
+            }
+        }
+        excluded.push(last..self.chars.len() - removed);
+        excluded.retain(|range| !range.is_empty());
+        excluded
+    }
+}
+
+//(タグの種類, 閉じタグか, 文字数)
+fn match_tag(chars: &[char], i: usize) -> Option<(usize, bool, usize)> {
+    TAGS.iter().enumerate().find_map(|(tag, (open, close))| {
+        [(open, false), (close, true)]
+            .into_iter()
+            .find(|(pattern, _)| starts_with_ignore_case(chars, i, pattern))
+            .map(|(pattern, is_close)| (tag, is_close, pattern.chars().count()))
+    })
+}
+
+fn starts_with_ignore_case(chars: &[char], i: usize, pattern: &str) -> bool {
+    pattern
+        .chars()
+        .enumerate()
+        .all(|(k, p)| chars.get(i + k).is_some_and(|c| c.eq_ignore_ascii_case(&p)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keep_tags() {
+        let markup = Markup::parse("HI <albhed>YOU</albhed> [ab]あ[/ab]!").unwrap();
+        assert_eq!(
+            markup.text(TagHandling::Keep),
+            "HI <albhed>YOU</albhed> [ab]あ[/ab]!"
+        );
+        assert_eq!(
+            markup.excluded(TagHandling::Keep),
+            vec![0..11, 14..28, 29..35]
+        );
+    }
+
+    #[test]
+    fn test_parse_strip_tags() {
+        let markup = Markup::parse("HI <ALBHED>YOU</ALBHED> [ab]あ[/ab]!").unwrap();
+        assert_eq!(markup.text(TagHandling::Strip), "HI YOU あ!");
+        assert_eq!(markup.excluded(TagHandling::Strip), vec![0..3, 6..7, 8..9]);
+    }
+
+    #[test]
+    fn test_strip_many_segments_in_linear_time() {
+        let text = "[ab]a[/ab] ".repeat(100_000);
+        let started = std::time::Instant::now();
+        let markup = Markup::parse(&text).unwrap();
+        assert_eq!(markup.text(TagHandling::Strip), "a ".repeat(100_000));
+        assert_eq!(markup.excluded(TagHandling::Strip).len(), 100_000);
+        assert!(started.elapsed().as_secs() < 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Markup::parse("<albhed>A [ab]B[/ab]</albhed>")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Nested tag [ab] at 10 inside tag opened at 0"
+        );
+        assert_eq!(
+            Markup::parse("A <albhed>B").err().unwrap().to_string(),
+            "Unclosed tag <albhed> at 2"
+        );
+        assert_eq!(
+            Markup::parse("A [/ab]").err().unwrap().to_string(),
+            "Unexpected closing tag [/ab] at 2"
+        );
+        assert_eq!(
+            Markup::parse("<albhed>A[/ab]").err().unwrap().to_string(),
+            "Tag <albhed> at 0 closed by [/ab] at 9"
+        );
+    }
+}
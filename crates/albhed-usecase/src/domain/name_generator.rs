@@ -0,0 +1,339 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::domain::entities::{Language, SubstitutionTable};
+use crate::domain::error::DomainError;
+use crate::domain::random::SeededRandom;
+use crate::domain::vocabulary::ENGLISH_WORDS;
+
+//ゲームに登場するアルベド族の名前（平文）。単語と同じように暗号化してから音節を数える
+const CANON_NAMES: [&str; 6] = ["RIKKU", "CID", "KEYAKKU", "ISKEN", "RIN", "SHINRA"];
+const VOWELS: &str = "AEIOUY";
+//性別を連想させる語尾。with_neutral_style はこの語尾を避けるだけ
+const GENDERED_ENDINGS: [&str; 5] = ["A", "O", "IA", "US", "INE"];
+const MIN_SYLLABLES: usize = 1;
+const MAX_SYLLABLES: usize = 4;
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 12;
+const MAX_ATTEMPTS: usize = 1000;
+
+pub struct GeneratedName {
+    name: String,
+    meaning: Option<String>,
+}
+
+impl GeneratedName {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //復号すると英単語になるときだけSomeになる
+    pub fn meaning(&self) -> Option<&str> {
+        self.meaning.as_deref()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NameOptions {
+    syllables: usize,
+    neutral: bool,
+    seed: u64,
+}
+
+impl NameOptions {
+    pub fn new(seed: u64) -> Self {
+        NameOptions {
+            syllables: 2,
+            neutral: false,
+            seed,
+        }
+    }
+
+    pub fn with_syllables(mut self, syllables: usize) -> Self {
+        self.syllables = syllables;
+        self
+    }
+
+    //GENDERED_ENDINGS で終わる名前を避ける。名前全体の印象までは見ない
+    pub fn with_neutral_style(mut self) -> Self {
+        self.neutral = true;
+        self
+    }
+
+    pub fn syllables(&self) -> usize {
+        self.syllables
+    }
+
+    pub fn neutral(&self) -> bool {
+        self.neutral
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+//暗号化した単語の音節を語頭・語中・語末ごとに数え、その頻度で名前を組み立てる
+pub struct NameGenerator {
+    initial: Vec<(String, usize)>,
+    medial: Vec<(String, usize)>,
+    last: Vec<(String, usize)>,
+    single: Vec<(String, usize)>,
+    vocabulary: HashSet<String>,
+}
+
+impl NameGenerator {
+    pub fn new() -> Self {
+        Self::with_corpus(&CANON_NAMES, ENGLISH_WORDS)
+    }
+
+    //names は名前、words は英単語で、どちらも平文
+    //会話文のコーパスは持っていないので、スペルチェック用の英単語リストで代わりにする
+    pub fn with_corpus(names: &[&str], words: &[&str]) -> Self {
+        let table = SubstitutionTable::albhed().restricted_to(&[Language::English]);
+        let mut initial = BTreeMap::new();
+        let mut medial = BTreeMap::new();
+        let mut last = BTreeMap::new();
+        let mut single = BTreeMap::new();
+        let vocabulary: HashSet<String> = words.iter().map(|word| word.to_uppercase()).collect();
+
+        let corpus = names
+            .iter()
+            .map(|name| name.to_uppercase())
+            .chain(vocabulary.iter().cloned())
+            .map(|word| {
+                word.chars()
+                    .map(|c| table.encode_char(c))
+                    .collect::<String>()
+            });
+        for word in corpus {
+            let syllables = syllabify(&word);
+            match syllables.len() {
+                0 => {}
+                1 => *single.entry(syllables[0].clone()).or_insert(0) += 1,
+                n => {
+                    *initial.entry(syllables[0].clone()).or_insert(0) += 1;
+                    for syllable in &syllables[1..n - 1] {
+                        *medial.entry(syllable.clone()).or_insert(0) += 1;
+                    }
+                    *last.entry(syllables[n - 1].clone()).or_insert(0) += 1;
+                }
+            }
+        }
+
+        NameGenerator {
+            initial: initial.into_iter().collect(),
+            medial: medial.into_iter().collect(),
+            last: last.into_iter().collect(),
+            single: single.into_iter().collect(),
+            vocabulary,
+        }
+    }
+
+    pub fn generate(
+        &self,
+        options: &NameOptions,
+        count: usize,
+    ) -> Result<Vec<GeneratedName>, DomainError> {
+        if !(MIN_SYLLABLES..=MAX_SYLLABLES).contains(&options.syllables()) {
+            return Err(DomainError::InvalidSyllableCount {
+                min: MIN_SYLLABLES,
+                max: MAX_SYLLABLES,
+            });
+        }
+
+        let table = SubstitutionTable::albhed();
+        let mut random = SeededRandom::new(options.seed());
+        let mut names: Vec<GeneratedName> = Vec::new();
+        while names.len() < count {
+            let name = (0..MAX_ATTEMPTS)
+                .map(|_| self.compose(options.syllables(), &mut random))
+                .find(|name| {
+                    is_pronounceable(name)
+                        && !(options.neutral() && has_gendered_ending(name))
+                        && !names
+                            .iter()
+                            .any(|generated| generated.name == capitalize(name))
+                })
+                .ok_or(DomainError::NameGenerationExhausted)?;
+
+            let decoded: String = name.chars().map(|c| table.decode_char(c)).collect();
+            names.push(GeneratedName {
+                name: capitalize(&name),
+                meaning: self
+                    .vocabulary
+                    .contains(&decoded)
+                    .then(|| decoded.to_lowercase()),
+            });
+        }
+        Ok(names)
+    }
+
+    fn compose(&self, syllables: usize, random: &mut SeededRandom) -> String {
+        if syllables == 1 {
+            return random
+                .pick_weighted(&self.single)
+                .unwrap_or_default()
+                .to_string();
+        }
+        let mut name = random
+            .pick_weighted(&self.initial)
+            .unwrap_or_default()
+            .to_string();
+        for _ in 0..syllables - 2 {
+            name.push_str(random.pick_weighted(&self.medial).unwrap_or_default());
+        }
+        name.push_str(random.pick_weighted(&self.last).unwrap_or_default());
+        name
+    }
+}
+
+impl Default for NameGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//母音の連続ごとに音節を作る。母音の間の子音は最後の一つを次の音節の頭に回す
+fn syllabify(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().filter(char::is_ascii_alphabetic).collect();
+    let is_vowel = |k: usize| VOWELS.contains(chars[k]);
+    let nuclei: Vec<usize> = (0..chars.len())
+        .filter(|&k| is_vowel(k) && (k == 0 || !is_vowel(k - 1)))
+        .collect();
+    if nuclei.is_empty() {
+        return Vec::new();
+    }
+
+    let mut boundaries = vec![0];
+    boundaries.extend(nuclei.iter().skip(1).map(|&nucleus| nucleus - 1));
+    boundaries.push(chars.len());
+    boundaries
+        .windows(2)
+        .map(|range| chars[range[0]..range[1]].iter().collect())
+        .collect()
+}
+
+//子音や母音が三つ以上続くものは読みにくいので避ける
+fn is_pronounceable(name: &str) -> bool {
+    let length = name.chars().count();
+    if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+        return false;
+    }
+    let mut run = 0;
+    let mut previous_vowel = None;
+    for c in name.chars() {
+        let vowel = VOWELS.contains(c);
+        run = if previous_vowel == Some(vowel) {
+            run + 1
+        } else {
+            1
+        };
+        if run > 2 {
+            return false;
+        }
+        previous_vowel = Some(vowel);
+    }
+    true
+}
+
+fn has_gendered_ending(name: &str) -> bool {
+    GENDERED_ENDINGS.iter().any(|ending| name.ends_with(ending))
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syllabify() {
+        assert_eq!(syllabify("KEYAKKU"), ["KEYAK", "KU"]);
+        assert_eq!(syllabify("YMPRAT"), ["YMP", "RAT"]);
+        assert_eq!(syllabify("CID"), ["CID"]);
+        assert!(syllabify("PST").is_empty());
+    }
+
+    #[test]
+    fn test_generate_is_reproducible() {
+        let generator = NameGenerator::new();
+        let options = NameOptions::new(42).with_syllables(3);
+        let first: Vec<String> = generator
+            .generate(&options, 5)
+            .unwrap()
+            .iter()
+            .map(|name| name.name().to_string())
+            .collect();
+        let second: Vec<String> = generator
+            .generate(&options, 5)
+            .unwrap()
+            .iter()
+            .map(|name| name.name().to_string())
+            .collect();
+
+        assert_eq!(first, second);
+        assert_eq!(first.iter().collect::<HashSet<_>>().len(), 5);
+        for name in &first {
+            assert!(is_pronounceable(&name.to_uppercase()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_generate_neutral_style() {
+        let generator = NameGenerator::new();
+        let options = NameOptions::new(7).with_neutral_style();
+        for name in generator.generate(&options, 20).unwrap() {
+            assert!(
+                !has_gendered_ending(&name.name().to_uppercase()),
+                "{}",
+                name.name()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_generate_meaning() {
+        //一音節の単語しかなければ、必ず元の英単語に戻る
+        let generator = NameGenerator::with_corpus(&[], &["CAT", "DOG"]);
+        let names = generator
+            .generate(&NameOptions::new(1).with_syllables(1), 2)
+            .unwrap();
+        let mut pairs: Vec<(&str, Option<&str>)> = names
+            .iter()
+            .map(|name| (name.name(), name.meaning()))
+            .collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![("Lyd", Some("cat")), ("Tuk", Some("dog"))]);
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_generate_from_enciphered_names() {
+        //平文の「Rikku」を暗号化した「NEGGI」の音節から組み立てる
+        let generator = NameGenerator::with_corpus(&["Rikku"], &[]);
+        let names = generator.generate(&NameOptions::new(3), 1).unwrap();
+        assert_eq!(names[0].name(), "Neggi");
+        assert_eq!(names[0].meaning(), None);
+    }
+
+    #[test]
+    fn test_generate_invalid_syllables() {
+        let generator = NameGenerator::new();
+        assert!(generator
+            .generate(&NameOptions::new(1).with_syllables(0), 1)
+            .is_err());
+        assert!(generator
+            .generate(&NameOptions::new(1).with_syllables(5), 1)
+            .is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+
+use rayon::prelude::*;
+use unicode_segmentation::GraphemeCursor;
+
+use crate::domain::entities::SubstitutionTable;
+
+//1つのスレッドに渡す大きさの目安（バイト）
+pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+
+//書記素クラスタの境界で、chunk_size バイト前後ごとに区切る
+pub fn split_chunks(text: &str, chunk_size: usize) -> Vec<&str> {
+    let mut chunks = Vec::with_capacity(text.len() / chunk_size.max(1) + 1);
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + chunk_size.max(1)).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        //境界の判定に前の文字を見ることがあるので、文字列全体を渡す
+        let mut cursor = GraphemeCursor::new(end, text.len(), true);
+        if !cursor.is_boundary(text, 0).unwrap() {
+            end = cursor.next_boundary(text, 0).unwrap().unwrap_or(text.len());
+        }
+        chunks.push(&text[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+//区切った塊をスレッドプールで変換し、順番どおりにつなぐ。結果は1つずつ変換したときと同じ
+pub fn encode<'a>(table: &SubstitutionTable, text: &'a str, chunk_size: usize) -> Cow<'a, str> {
+    translate(text, chunk_size, |chunk| table.encode(chunk))
+}
+
+pub fn decode<'a>(table: &SubstitutionTable, text: &'a str, chunk_size: usize) -> Cow<'a, str> {
+    translate(text, chunk_size, |chunk| table.decode(chunk))
+}
+
+fn translate<'a>(
+    text: &'a str,
+    chunk_size: usize,
+    convert: impl Fn(&'a str) -> Cow<'a, str> + Sync + Send,
+) -> Cow<'a, str> {
+    let converted: Vec<Cow<str>> = split_chunks(text, chunk_size)
+        .into_par_iter()
+        .map(convert)
+        .collect();
+    if converted
+        .iter()
+        .all(|chunk| matches!(chunk, Cow::Borrowed(_)))
+    {
+        return Cow::Borrowed(text);
+    }
+    let mut s = String::with_capacity(converted.iter().map(|chunk| chunk.len()).sum());
+    for chunk in &converted {
+        s.push_str(chunk);
+    }
+    Cow::Owned(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use super::*;
+
+    const MIXED: &str = "HELLO か\u{3099}は\u{309A} CAFE\u{0301}\r\n🇯🇵🇺🇸 👨\u{200D}👩\u{200D}👧 \
+                         やっちまうか！？ N\u{0303}O\u{0308}\u{0301} 👍\u{1F3FD}\n";
+
+    #[test]
+    fn test_split_chunks_on_grapheme_boundaries() {
+        let text = MIXED.repeat(3);
+        for chunk_size in 1..40 {
+            let chunks = split_chunks(&text, chunk_size);
+            assert_eq!(chunks.concat(), text);
+            assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+            //区切りが書記素クラスタの途中にない
+            let clusters: usize = chunks
+                .iter()
+                .map(|chunk| chunk.graphemes(true).count())
+                .sum();
+            assert_eq!(
+                clusters,
+                text.graphemes(true).count(),
+                "chunk_size: {}",
+                chunk_size
+            );
+        }
+        assert!(split_chunks("", 4).is_empty());
+    }
+
+    #[test]
+    fn test_same_as_sequential() {
+        let table = SubstitutionTable::albhed();
+        let text = MIXED.repeat(50);
+        let sequential = table.encode(&text);
+        for chunk_size in [1, 7, 64, 1000, DEFAULT_CHUNK_SIZE] {
+            let encoded = encode(table, &text, chunk_size);
+            assert_eq!(encoded, sequential, "chunk_size: {}", chunk_size);
+            assert_eq!(decode(table, &encoded, chunk_size), table.decode(&encoded));
+        }
+    }
+
+    #[test]
+    fn test_unchanged_text_is_borrowed() {
+        let text = "1234 5678 !?".repeat(10);
+        assert!(matches!(
+            encode(SubstitutionTable::albhed(), &text, 8),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,476 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::domain::error::DomainError;
+use crate::domain::lint::normalize_homoglyphs;
+
+//暗号化の前後に入れる処理の一段
+pub trait TextProcessor: Send + Sync {
+    fn name(&self) -> &str;
+    fn process(&self, text: &str) -> Result<String, DomainError>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    //暗号化・復号の前
+    Pre,
+    //暗号化・復号の後
+    Post,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextChange {
+    start: usize,
+    end: usize,
+    before: String,
+    after: String,
+}
+
+impl TextChange {
+    //段に入る前のテキスト中の文字（char）単位の位置
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn before(&self) -> &str {
+        &self.before
+    }
+
+    pub fn after(&self) -> &str {
+        &self.after
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StageReport {
+    stage: String,
+    phase: Phase,
+    changes: Vec<TextChange>,
+}
+
+impl StageReport {
+    pub fn stage(&self) -> &str {
+        &self.stage
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn changes(&self) -> &[TextChange] {
+        &self.changes
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    pre: Vec<Arc<dyn TextProcessor>>,
+    post: Vec<Arc<dyn TextProcessor>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //名前の付いた組み合わせ
+    pub fn profile(name: &str) -> Result<Self, DomainError> {
+        let (pre, post): (&[&str], &[&str]) = match name {
+            "plain" => (&[], &[]),
+            "normalize" => (
+                &["trim", "collapse_whitespace", "fullwidth", "homoglyphs"],
+                &[],
+            ),
+            "game" => (
+                &[
+                    "trim",
+                    "collapse_whitespace",
+                    "fullwidth",
+                    "homoglyphs",
+                    "uppercase",
+                    "hiragana",
+                ],
+                &[],
+            ),
+            _ => return Err(DomainError::UnknownPipelineProfile(name.to_string())),
+        };
+        Self::from_names(pre, post)
+    }
+
+    pub fn from_names(pre: &[&str], post: &[&str]) -> Result<Self, DomainError> {
+        let mut pipeline = Pipeline::new();
+        for name in pre {
+            pipeline = pipeline.with_pre(processor(name)?);
+        }
+        for name in post {
+            pipeline = pipeline.with_post(processor(name)?);
+        }
+        Ok(pipeline)
+    }
+
+    pub fn with_pre(mut self, processor: Arc<dyn TextProcessor>) -> Self {
+        self.pre.push(processor);
+        self
+    }
+
+    pub fn with_post(mut self, processor: Arc<dyn TextProcessor>) -> Self {
+        self.post.push(processor);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pre.is_empty() && self.post.is_empty()
+    }
+
+    pub fn run(&self, phase: Phase, text: &str) -> Result<(String, Vec<StageReport>), DomainError> {
+        self.run_shielded(phase, text, &Shield::default())
+    }
+
+    //shield で隠した範囲は目印のまま各段に渡し、段が目印を消したり増やしたりしたら失敗にする
+    //変更の報告は目印を元に戻したテキストで作る
+    pub fn run_shielded(
+        &self,
+        phase: Phase,
+        text: &str,
+        shield: &Shield,
+    ) -> Result<(String, Vec<StageReport>), DomainError> {
+        let stages = match phase {
+            Phase::Pre => &self.pre,
+            Phase::Post => &self.post,
+        };
+        let mut text = text.to_string();
+        let mut reports = Vec::new();
+        for stage in stages {
+            let processed = stage.process(&text)?;
+            if shield.count(&processed) != shield.len() {
+                return Err(DomainError::StageFailed {
+                    stage: stage.name().to_string(),
+                    source: "Protected text was removed or duplicated".into(),
+                });
+            }
+            reports.push(StageReport {
+                stage: stage.name().to_string(),
+                phase,
+                changes: diff(&shield.restore(&text).0, &shield.restore(&processed).0),
+            });
+            text = processed;
+        }
+        Ok((text, reports))
+    }
+}
+
+//保護した範囲を一文字の目印に置き換え、前後の処理や変換で変わらないようにする
+#[derive(Clone, Debug, Default)]
+pub struct Shield {
+    marker: char,
+    //まとめた範囲（入力の文字位置）と、隠した文字列（範囲の順）
+    ranges: Vec<Range<usize>>,
+    originals: Vec<String>,
+}
+
+impl Shield {
+    //重なったり隣り合ったりする範囲はまとめて一つの目印にする
+    pub fn new(text: &str, ranges: &[Range<usize>]) -> (Self, String) {
+        let chars: Vec<char> = text.chars().collect();
+        //テキストにない非文字か私用領域の文字を目印にする
+        let marker = ('\u{FDD0}'..='\u{FDEF}')
+            .chain('\u{E000}'..='\u{F8FF}')
+            .find(|c| !chars.contains(c))
+            .unwrap_or('\u{FDD0}');
+
+        let mut sorted: Vec<Range<usize>> = ranges
+            .iter()
+            .map(|range| range.start..range.end.min(chars.len()))
+            .filter(|range| range.start < range.end)
+            .collect();
+        sorted.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in sorted {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        let mut shielded = String::with_capacity(text.len());
+        let mut last = 0;
+        for range in &merged {
+            shielded.extend(&chars[last..range.start]);
+            shielded.push(marker);
+            last = range.end;
+        }
+        shielded.extend(&chars[last..]);
+
+        let originals = merged
+            .iter()
+            .map(|range| chars[range.clone()].iter().collect())
+            .collect();
+        let shield = Shield {
+            marker,
+            ranges: merged,
+            originals,
+        };
+        (shield, shielded)
+    }
+
+    pub fn len(&self) -> usize {
+        self.originals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.originals.is_empty()
+    }
+
+    fn count(&self, text: &str) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        text.chars().filter(|&c| c == self.marker).count()
+    }
+
+    //目印の位置（文字位置）
+    pub fn positions(&self, text: &str) -> Vec<Range<usize>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        text.chars()
+            .enumerate()
+            .filter(|&(_, c)| c == self.marker)
+            .map(|(k, _)| k..k + 1)
+            .collect()
+    }
+
+    //目印を元の文字列に戻し、戻した文字列の位置を new で渡した範囲の順に返す
+    pub fn restore(&self, text: &str) -> (String, Vec<Range<usize>>) {
+        if self.is_empty() {
+            return (text.to_string(), Vec::new());
+        }
+        let mut restored = String::with_capacity(text.len());
+        let mut ranges = Vec::with_capacity(self.len());
+        let mut position = 0;
+        let mut originals = self.originals.iter();
+        for c in text.chars() {
+            if c == self.marker {
+                if let Some(original) = originals.next() {
+                    let len = original.chars().count();
+                    restored.push_str(original);
+                    ranges.push(position..position + len);
+                    position += len;
+                    continue;
+                }
+            }
+            restored.push(c);
+            position += 1;
+        }
+        (restored, ranges)
+    }
+
+    //new で渡した範囲が、restore で戻した文字列のどこにあるか
+    pub fn locate(&self, range: &Range<usize>, restored: &[Range<usize>]) -> Option<Range<usize>> {
+        let index = self
+            .ranges
+            .partition_point(|merged| merged.end <= range.start);
+        let merged = self.ranges.get(index)?;
+        if range.start < merged.start || merged.end < range.end {
+            return None;
+        }
+        let start = restored.get(index)?.start + range.start - merged.start;
+        Some(start..start + range.len())
+    }
+}
+
+//文字数が同じなら違う文字の並びごとに、違えば前後の共通部分を除いた一か所として返す
+fn diff(before: &str, after: &str) -> Vec<TextChange> {
+    let before: Vec<char> = before.chars().collect();
+    let after: Vec<char> = after.chars().collect();
+    let change = |start: usize, end: usize, after_end: usize| TextChange {
+        start,
+        end,
+        before: before[start..end].iter().collect(),
+        after: after[start..after_end].iter().collect(),
+    };
+
+    if before.len() == after.len() {
+        let mut changes = Vec::new();
+        let mut i = 0;
+        while i < before.len() {
+            if before[i] == after[i] {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < before.len() && before[i] != after[i] {
+                i += 1;
+            }
+            changes.push(change(start, i, i));
+        }
+        return changes;
+    }
+
+    let prefix = before
+        .iter()
+        .zip(&after)
+        .take_while(|(b, a)| b == a)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(b, a)| b == a)
+        .count();
+    vec![change(prefix, before.len() - suffix, after.len() - suffix)]
+}
+
+struct FnProcessor {
+    name: String,
+    convert: fn(&str) -> String,
+}
+
+impl TextProcessor for FnProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(&self, text: &str) -> Result<String, DomainError> {
+        Ok((self.convert)(text))
+    }
+}
+
+//組み込みの処理を名前で探す
+pub fn processor(name: &str) -> Result<Arc<dyn TextProcessor>, DomainError> {
+    let convert: fn(&str) -> String = match name {
+        "trim" => |text| text.trim().to_string(),
+        "collapse_whitespace" => |text| text.split_whitespace().collect::<Vec<_>>().join(" "),
+        "fullwidth" => |text| text.chars().map(from_fullwidth).collect(),
+        "homoglyphs" => normalize_homoglyphs,
+        "uppercase" => |text| text.to_uppercase(),
+        "lowercase" => |text| text.to_lowercase(),
+        "hiragana" => |text| text.chars().map(to_hiragana).collect(),
+        _ => return Err(DomainError::UnknownPipelineStage(name.to_string())),
+    };
+    Ok(Arc::new(FnProcessor {
+        name: name.to_string(),
+        convert,
+    }))
+}
+
+//全角英数記号と全角スペースを半角にする
+fn from_fullwidth(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
+
+//表はひらがなを変換するので、カタカナをひらがなにする
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_reports_changes() {
+        let pipeline = Pipeline::from_names(&["trim", "uppercase"], &["lowercase"]).unwrap();
+        let (text, reports) = pipeline.run(Phase::Pre, "  hi HO ").unwrap();
+        assert_eq!(text, "HI HO");
+
+        let changes: Vec<(&str, usize, usize, &str, &str)> = reports
+            .iter()
+            .flat_map(|report| {
+                report
+                    .changes()
+                    .iter()
+                    .map(|c| (report.stage(), c.start(), c.end(), c.before(), c.after()))
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("trim", 0, 8, "  hi HO ", "hi HO"),
+                ("uppercase", 0, 2, "hi", "HI"),
+            ]
+        );
+
+        let (text, reports) = pipeline.run(Phase::Post, "RE").unwrap();
+        assert_eq!((text.as_str(), reports[0].phase()), ("re", Phase::Post));
+    }
+
+    #[test]
+    fn test_builtin_stages() {
+        let pipeline = Pipeline::profile("game").unwrap();
+        let (text, _) = pipeline
+            .run(Phase::Pre, " ｈｅｌｌｏ　  \u{0410}LBHED カタカナ")
+            .unwrap();
+        assert_eq!(text, "HELLO ALBHED かたかな");
+    }
+
+    #[test]
+    fn test_run_shielded() {
+        let text = " ｈｅｌｌｏ https://example.com/Path カタカナ";
+        let (shield, shielded) = Shield::new(text, &[7..31, 10..12, 40..50]);
+        assert_eq!(shield.len(), 1);
+
+        let pipeline = Pipeline::profile("game").unwrap();
+        let (processed, reports) = pipeline
+            .run_shielded(Phase::Pre, &shielded, &shield)
+            .unwrap();
+        assert_eq!(shield.positions(&processed), vec![6..7]);
+        let (restored, ranges) = shield.restore(&processed);
+        assert_eq!(restored, "HELLO https://example.com/Path かたかな");
+        assert_eq!(ranges, vec![6..30]);
+        assert_eq!(shield.locate(&(10..12), &ranges), Some(9..11));
+        assert_eq!(shield.locate(&(7..31), &ranges), Some(6..30));
+        assert_eq!(shield.locate(&(40..50), &ranges), None);
+
+        let trim = &reports[0].changes()[0];
+        assert_eq!((trim.start(), trim.end(), trim.before()), (0, 1, " "));
+    }
+
+    #[test]
+    fn test_run_shielded_rejects_removed_markers() {
+        struct Drop;
+
+        impl TextProcessor for Drop {
+            fn name(&self) -> &str {
+                "drop"
+            }
+
+            fn process(&self, text: &str) -> Result<String, DomainError> {
+                Ok(text.chars().filter(char::is_ascii).collect())
+            }
+        }
+
+        let (shield, shielded) = Shield::new("HI @RIKKU {x}", &[3..9, 10..13]);
+        let pipeline = Pipeline::new().with_pre(Arc::new(Drop));
+        let error = pipeline
+            .run_shielded(Phase::Pre, &shielded, &shield)
+            .unwrap_err();
+        assert_eq!(error.code(), "pipeline_stage_failed");
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        assert_eq!(
+            Pipeline::profile("fancy").err().unwrap().to_string(),
+            "Unknown pipeline profile: fancy"
+        );
+        assert_eq!(
+            Pipeline::from_names(&["rot13"], &[])
+                .err()
+                .unwrap()
+                .to_string(),
+            "Unknown pipeline stage: rot13"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::iter;
+use std::ops::Range;
+
+const PLURAL_TYPES: [&str; 3] = ["plural", "select", "selectordinal"];
+const PRINTF_FLAGS: &str = "-+#0'";
+const PRINTF_LENGTHS: [&str; 9] = ["hh", "ll", "h", "l", "L", "q", "j", "z", "t"];
+const PRINTF_CONVERSIONS: &str = "diouxXeEfFgGaAcspn@";
+//これより深く入れ子になったplural・selectはプレースホルダとみなさない
+const MAX_NESTING: usize = 16;
+
+//対応する閉じ括弧と、各位置から次の "}}" の位置をテキスト全体で一度だけ求めておく
+//閉じていない括弧があっても、開き括弧ごとに末尾まで探し直さずに済む
+pub struct BraceIndex {
+    closing: Vec<Option<usize>>,
+    next_double_closing: Vec<Option<usize>>,
+}
+
+impl BraceIndex {
+    pub fn new(chars: &[char]) -> Self {
+        let mut closing = vec![None; chars.len()];
+        let mut open = Vec::new();
+        for (k, &c) in chars.iter().enumerate() {
+            match c {
+                '{' => open.push(k),
+                '}' => {
+                    if let Some(o) = open.pop() {
+                        closing[o] = Some(k);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut next_double_closing = vec![None; chars.len() + 1];
+        for k in (0..chars.len().saturating_sub(1)).rev() {
+            next_double_closing[k] = if chars[k] == '}' && chars[k + 1] == '}' {
+                Some(k)
+            } else {
+                next_double_closing[k + 1]
+            };
+        }
+
+        BraceIndex {
+            closing,
+            next_double_closing,
+        }
+    }
+
+    fn closing(&self, open: usize) -> Option<usize> {
+        self.closing.get(open).copied().flatten()
+    }
+
+    fn next_double_closing(&self, from: usize) -> Option<usize> {
+        self.next_double_closing.get(from).copied().flatten()
+    }
+}
+
+//位置 i から始まるプレースホルダを読み、(終わりの位置, 変換しない範囲) を返す
+//ICUのplural・selectは分岐の中の文字列だけを変換対象に残す
+pub fn match_placeholder(
+    chars: &[char],
+    braces: &BraceIndex,
+    i: usize,
+) -> Option<(usize, Vec<Range<usize>>)> {
+    match_nested(chars, braces, i, 0)
+}
+
+fn match_nested(
+    chars: &[char],
+    braces: &BraceIndex,
+    i: usize,
+    depth: usize,
+) -> Option<(usize, Vec<Range<usize>>)> {
+    match chars[i] {
+        '{' => match_argument(chars, braces, i, depth),
+        '%' => match_printf(chars, i).map(|end| (end, iter::once(i..end).collect())),
+        _ => None,
+    }
+}
+
+//{{count}} / {name} / {0, number} / {count, plural, ...}
+fn match_argument(
+    chars: &[char],
+    braces: &BraceIndex,
+    i: usize,
+    depth: usize,
+) -> Option<(usize, Vec<Range<usize>>)> {
+    if chars.get(i + 1) == Some(&'{') {
+        let close = braces.next_double_closing(i + 2)?;
+        return Some((close + 2, iter::once(i..close + 2).collect()));
+    }
+
+    let mut j = skip_whitespace(chars, i + 1);
+    let name = identifier_length(chars, j);
+    if name == 0 {
+        return None;
+    }
+    j = skip_whitespace(chars, j + name);
+    match chars.get(j)? {
+        '}' => return Some((j + 1, iter::once(i..j + 1).collect())),
+        ',' => j = skip_whitespace(chars, j + 1),
+        _ => return None,
+    }
+
+    let kind_length = identifier_length(chars, j);
+    let kind: String = chars[j..j + kind_length].iter().collect();
+    j = skip_whitespace(chars, j + kind_length);
+    if !PLURAL_TYPES.contains(&kind.as_str()) {
+        let close = braces.closing(i)?;
+        return Some((close + 1, iter::once(i..close + 1).collect()));
+    }
+    if chars.get(j) != Some(&',') || depth >= MAX_NESTING {
+        return None;
+    }
+    j += 1;
+
+    let mut protected = Vec::new();
+    let mut protect_from = i;
+    loop {
+        j = skip_whitespace(chars, j);
+        match chars.get(j)? {
+            '}' => {
+                protected.push(protect_from..j + 1);
+                return Some((j + 1, protected));
+            }
+            '=' => j += 1 + run_length(chars, j + 1, |c| c.is_ascii_digit()),
+            _ if starts_with(chars, j, "offset:") => {
+                j = skip_whitespace(chars, j + "offset:".len());
+                j += run_length(chars, j, |c| c.is_ascii_digit());
+                continue;
+            }
+            _ => {
+                let selector = identifier_length(chars, j);
+                if selector == 0 {
+                    return None;
+                }
+                j += selector;
+            }
+        }
+        j = skip_whitespace(chars, j);
+        if chars.get(j) != Some(&'{') {
+            return None;
+        }
+        protected.push(protect_from..j + 1);
+
+        let (close, nested) = match_message(chars, braces, j + 1, kind != "select", depth + 1)?;
+        protected.extend(nested);
+        protect_from = close;
+        j = close + 1;
+    }
+}
+
+//分岐の中の文字列を閉じ括弧まで読む。pluralでは # も数値に置き換わるので残す
+fn match_message(
+    chars: &[char],
+    braces: &BraceIndex,
+    start: usize,
+    plural: bool,
+    depth: usize,
+) -> Option<(usize, Vec<Range<usize>>)> {
+    let mut protected = Vec::new();
+    let mut k = start;
+    while k < chars.len() {
+        match chars[k] {
+            '}' => return Some((k, protected)),
+            '#' if plural => {
+                protected.push(k..k + 1);
+                k += 1;
+            }
+            '{' | '%' => match match_nested(chars, braces, k, depth) {
+                Some((end, nested)) => {
+                    protected.extend(nested);
+                    k = end;
+                }
+                None if chars[k] == '{' => return None,
+                None => k += 1,
+            },
+            _ => k += 1,
+        }
+    }
+    None
+}
+
+//%s / %1$d / %-5.2f / %%
+fn match_printf(chars: &[char], i: usize) -> Option<usize> {
+    if chars.get(i + 1) == Some(&'%') {
+        return Some(i + 2);
+    }
+
+    let mut j = i + 1;
+    let position = run_length(chars, j, |c| c.is_ascii_digit());
+    if position > 0 && chars.get(j + position) == Some(&'$') {
+        j += position + 1;
+    }
+    j += run_length(chars, j, |c| PRINTF_FLAGS.contains(c));
+    j += run_length(chars, j, |c| c.is_ascii_digit() || c == '*');
+    if chars.get(j) == Some(&'.') {
+        j += 1 + run_length(chars, j + 1, |c| c.is_ascii_digit() || c == '*');
+    }
+    if let Some(length) = PRINTF_LENGTHS.iter().find(|l| starts_with(chars, j, l)) {
+        j += length.len();
+    }
+    chars
+        .get(j)
+        .is_some_and(|&c| PRINTF_CONVERSIONS.contains(c))
+        .then_some(j + 1)
+}
+
+fn identifier_length(chars: &[char], start: usize) -> usize {
+    run_length(chars, start, |c| c.is_alphanumeric() || c == '_')
+}
+
+fn skip_whitespace(chars: &[char], start: usize) -> usize {
+    start + run_length(chars, start, char::is_whitespace)
+}
+
+fn run_length(chars: &[char], start: usize, predicate: impl Fn(char) -> bool) -> usize {
+    chars
+        .get(start..)
+        .map_or(0, |rest| rest.iter().take_while(|&&c| predicate(c)).count())
+}
+
+fn starts_with(chars: &[char], start: usize, pattern: &str) -> bool {
+    pattern
+        .chars()
+        .enumerate()
+        .all(|(k, p)| chars.get(start + k) == Some(&p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protected_texts(text: &str) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let (_, ranges) = match_placeholder(&chars, &BraceIndex::new(&chars), 0).unwrap();
+        ranges
+            .into_iter()
+            .map(|range| chars[range].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_match_simple_placeholders() {
+        for text in [
+            "{name}",
+            "{{count}}",
+            "{ 0 , number , integer }",
+            "%s",
+            "%1$d",
+            "%-5.2f",
+            "%%",
+            "%lld",
+        ] {
+            assert_eq!(protected_texts(text), vec![text.to_string()]);
+        }
+    }
+
+    #[test]
+    fn test_match_plural_branches() {
+        let text = "{count, plural, =0 {NO ITEMS} one {# ITEM} other {# ITEMS FOR {name}}}";
+        assert_eq!(
+            protected_texts(text),
+            vec![
+                "{count, plural, =0 {",
+                "} one {",
+                "#",
+                "} other {",
+                "#",
+                "{name}",
+                "}}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_select_keeps_hash() {
+        let text = "{gender, select, female {SHE #1} other {THEY}}";
+        assert_eq!(
+            protected_texts(text),
+            vec!["{gender, select, female {", "} other {", "}}"]
+        );
+    }
+
+    #[test]
+    fn test_match_rejects_non_placeholders() {
+        for text in [
+            "{",
+            "{}",
+            "{ name",
+            "% OFF",
+            "%y",
+            "{count, plural, one {ITEM}",
+        ] {
+            let chars: Vec<char> = text.chars().collect();
+            let braces = BraceIndex::new(&chars);
+            assert!(match_placeholder(&chars, &braces, 0).is_none(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_match_deep_nesting_as_text() {
+        let matched = |depth: usize| {
+            let text = "{n, plural, other {".repeat(depth) + "HI" + &"}}".repeat(depth);
+            let chars: Vec<char> = text.chars().collect();
+            match_placeholder(&chars, &BraceIndex::new(&chars), 0)
+                .map(|(end, _)| (end, chars.len()))
+        };
+
+        let (end, len) = matched(MAX_NESTING).unwrap();
+        assert_eq!(end, len);
+        assert!(matched(MAX_NESTING + 1).is_none());
+        //深すぎる入れ子でもスタックを使い切らない
+        assert!(matched(100_000).is_none());
+    }
+
+    #[test]
+    fn test_match_unclosed_braces_in_linear_time() {
+        //閉じていない括弧が並んでも、開き括弧ごとに末尾まで探し直さない
+        for (unit, count) in [
+            ("{{", 200_000),
+            ("{", 200_000),
+            ("{0, number ", 100_000),
+            ("{n, plural, other {HI ", 20_000),
+        ] {
+            let chars: Vec<char> = unit.repeat(count).chars().collect();
+            let started = std::time::Instant::now();
+            let braces = BraceIndex::new(&chars);
+            let matched = (0..chars.len())
+                .filter(|&i| match_placeholder(&chars, &braces, i).is_some())
+                .count();
+            assert_eq!(matched, 0, "{}", unit);
+            assert!(started.elapsed().as_secs() < 2, "{}", unit);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//音の単位 (IPA, 読み方のつづり, 母音か)
+type Sound = (&'static str, &'static str, bool);
+
+//二文字で一つの音になるもの。一文字ずつ読むより先に試す
+const DIGRAPHS: [(&str, Sound); 17] = [
+    ("ai", ("aɪ", "eye", true)),
+    ("ay", ("aɪ", "eye", true)),
+    ("ei", ("eɪ", "ay", true)),
+    ("ey", ("eɪ", "ay", true)),
+    ("oi", ("ɔɪ", "oy", true)),
+    ("oy", ("ɔɪ", "oy", true)),
+    ("au", ("aʊ", "ow", true)),
+    ("aw", ("aʊ", "ow", true)),
+    ("oo", ("uː", "oo", true)),
+    ("ee", ("iː", "ee", true)),
+    ("ch", ("tʃ", "ch", false)),
+    ("sh", ("ʃ", "sh", false)),
+    ("th", ("θ", "th", false)),
+    ("ng", ("ŋ", "ng", false)),
+    ("ph", ("f", "f", false)),
+    ("ck", ("k", "k", false)),
+    ("qu", ("kw", "kw", false)),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordPronunciation {
+    start: usize,
+    end: usize,
+    word: String,
+    syllables: Vec<String>,
+    respelling: String,
+    ipa: String,
+}
+
+impl WordPronunciation {
+    //入力テキスト中の文字（char）単位の位置
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    pub fn syllables(&self) -> &[String] {
+        &self.syllables
+    }
+
+    //強勢のある音節を大文字にし、音節をハイフンでつないだ読み方（例: IHM-praht）
+    pub fn respelling(&self) -> &str {
+        &self.respelling
+    }
+
+    pub fn ipa(&self) -> &str {
+        &self.ipa
+    }
+}
+
+//ラテン文字のアルベド語を単語ごとに音節に分け、読み方とIPAを付ける
+//ゲームの声優の発音に合わせ、母音はほぼローマ字読み、強勢は第一音節に置く
+pub fn pronounce(text: &str) -> Vec<WordPronunciation> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_alphabetic() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && (chars[i].is_ascii_alphabetic() || chars[i] == '\'') {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        if let Some(pronunciation) = pronounce_word(&word, start, i) {
+            words.push(pronunciation);
+        }
+    }
+    words
+}
+
+fn pronounce_word(word: &str, start: usize, end: usize) -> Option<WordPronunciation> {
+    let letters: Vec<char> = word
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let sounds = to_sounds(&letters);
+    let syllables = syllabify(&sounds);
+    if syllables.is_empty() {
+        return None;
+    }
+
+    let respelled: Vec<String> = syllables
+        .iter()
+        .enumerate()
+        .map(|(k, syllable)| {
+            let spelling: String = syllable.iter().map(|sound| sound.1).collect();
+            if k == 0 && syllables.len() > 1 {
+                spelling.to_uppercase()
+            } else {
+                spelling
+            }
+        })
+        .collect();
+    let ipa: Vec<String> = syllables
+        .iter()
+        .map(|syllable| syllable.iter().map(|sound| sound.0).collect())
+        .collect();
+    let ipa = if syllables.len() > 1 {
+        format!("ˈ{}", ipa.join("."))
+    } else {
+        ipa.concat()
+    };
+
+    Some(WordPronunciation {
+        start,
+        end,
+        word: word.to_string(),
+        syllables: syllables
+            .iter()
+            .map(|syllable| syllable.iter().map(|sound| sound.1).collect())
+            .collect(),
+        respelling: respelled.join("-"),
+        ipa,
+    })
+}
+
+fn to_sounds(letters: &[char]) -> Vec<Sound> {
+    let mut sounds = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let pair: String = letters[i..letters.len().min(i + 2)].iter().collect();
+        if let Some((_, sound)) = DIGRAPHS.iter().find(|(digraph, _)| *digraph == pair) {
+            sounds.push(*sound);
+            i += 2;
+            continue;
+        }
+
+        let next = letters.get(i + 1).copied();
+        let next_is_vowel = next.is_some_and(|c| "aeiou".contains(c));
+        let sound = match letters[i] {
+            'a' => ("a", "ah", true),
+            'e' => ("e", "eh", true),
+            'i' => ("i", "ee", true),
+            'o' => ("o", "oh", true),
+            'u' => ("u", "oo", true),
+            //母音の前では半母音、それ以外では母音
+            'y' if next_is_vowel => ("j", "y", false),
+            'y' => ("ɪ", "ih", true),
+            'c' if next.is_some_and(|c| "eiy".contains(c)) => ("s", "s", false),
+            'c' => ("k", "k", false),
+            'g' => ("ɡ", "g", false),
+            'j' => ("dʒ", "j", false),
+            'q' => ("kw", "kw", false),
+            'r' => ("ɹ", "r", false),
+            'x' => ("ks", "ks", false),
+            'b' => ("b", "b", false),
+            'd' => ("d", "d", false),
+            'f' => ("f", "f", false),
+            'h' => ("h", "h", false),
+            'k' => ("k", "k", false),
+            'l' => ("l", "l", false),
+            'm' => ("m", "m", false),
+            'n' => ("n", "n", false),
+            'p' => ("p", "p", false),
+            's' => ("s", "s", false),
+            't' => ("t", "t", false),
+            'v' => ("v", "v", false),
+            'w' => ("w", "w", false),
+            'z' => ("z", "z", false),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        sounds.push(sound);
+        i += 1;
+    }
+    sounds
+}
+
+//母音ごとに音節を作る。母音の間の子音は最後の一つ（pr・dr などはその二つ）を次の音節の頭に回す
+fn syllabify(sounds: &[Sound]) -> Vec<Vec<Sound>> {
+    let nuclei: Vec<usize> = (0..sounds.len()).filter(|&k| sounds[k].2).collect();
+    if nuclei.is_empty() {
+        return if sounds.is_empty() {
+            Vec::new()
+        } else {
+            vec![sounds.to_vec()]
+        };
+    }
+
+    let mut boundaries = vec![0];
+    for pair in nuclei.windows(2) {
+        let consonants = pair[1] - pair[0] - 1;
+        let onset = match consonants {
+            0 => 0,
+            1 => 1,
+            _ if is_onset_cluster(sounds[pair[1] - 2], sounds[pair[1] - 1]) => 2,
+            _ => 1,
+        };
+        boundaries.push(pair[1] - onset);
+    }
+    boundaries.push(sounds.len());
+    boundaries
+        .windows(2)
+        .map(|range| sounds[range[0]..range[1]].to_vec())
+        .collect()
+}
+
+fn is_onset_cluster(first: Sound, second: Sound) -> bool {
+    ["p", "b", "t", "d", "k", "ɡ", "f", "θ"].contains(&first.0) && ["ɹ", "l"].contains(&second.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(text: &str) -> Vec<(String, String)> {
+        pronounce(text)
+            .iter()
+            .map(|word| (word.respelling().to_string(), word.ipa().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_pronounce_sentence() {
+        assert_eq!(
+            summary("Fryd ec dryd?"),
+            vec![
+                ("frihd".to_string(), "fɹɪd".to_string()),
+                ("ehk".to_string(), "ek".to_string()),
+                ("drihd".to_string(), "dɹɪd".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pronounce_syllables() {
+        let words = pronounce("YMPRAT");
+        assert_eq!(words[0].syllables(), ["ihm", "praht"]);
+        assert_eq!(words[0].respelling(), "IHM-praht");
+        assert_eq!(words[0].ipa(), "ˈɪm.pɹat");
+    }
+
+    #[test]
+    fn test_pronounce_skips_non_latin() {
+        let words = pronounce("ギアン oui");
+        assert_eq!(words.len(), 1);
+        assert_eq!((words[0].start(), words[0].end()), (4, 7));
+        assert_eq!(words[0].respelling(), "OH-oo-ee");
+    }
+}
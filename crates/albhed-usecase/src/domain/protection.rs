@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::iter;
+
+use crate::domain::placeholder::{match_placeholder, BraceIndex};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityKind {
+    Code,
+    Url,
+    Email,
+    Placeholder,
+    Mention,
+    Hashtag,
+}
+
+impl EntityKind {
+    //重なったときはこの順で優先する
+    pub const ALL: [EntityKind; 6] = [
+        EntityKind::Code,
+        EntityKind::Url,
+        EntityKind::Email,
+        EntityKind::Placeholder,
+        EntityKind::Mention,
+        EntityKind::Hashtag,
+    ];
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtectedSpan {
+    kind: EntityKind,
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl ProtectedSpan {
+    pub fn new(kind: EntityKind, start: usize, end: usize, text: &str) -> Self {
+        ProtectedSpan {
+            kind,
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> EntityKind {
+        self.kind
+    }
+
+    //入力テキスト中の文字（char）単位の位置
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+const URL_PREFIXES: [&str; 3] = ["https://", "http://", "www."];
+const URL_TRAILING_PUNCTUATION: &str = ".,!?;:'\")]}>";
+
+//変換から除外するURL・メールアドレス・プレースホルダ・メンション・ハッシュタグ・コードを探す
+pub fn find_entities(text: &str, kinds: &[EntityKind]) -> Vec<ProtectedSpan> {
+    let chars: Vec<char> = text.chars().collect();
+    let braces = BraceIndex::new(&chars);
+    let backticks = BacktickRuns::new(&chars);
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let found = EntityKind::ALL
+            .iter()
+            .filter(|kind| kinds.contains(kind))
+            .find_map(|&kind| {
+                let end = match kind {
+                    EntityKind::Code => match_code(&backticks, i),
+                    EntityKind::Url => match_url(&chars, i),
+                    EntityKind::Email => match_email(&chars, i),
+                    EntityKind::Placeholder => {
+                        return match_placeholder(&chars, &braces, i)
+                            .map(|(end, ranges)| (kind, end, ranges))
+                    }
+                    EntityKind::Mention => match_mention(&chars, i),
+                    EntityKind::Hashtag => match_hashtag(&chars, i),
+                }?;
+                Some((kind, end, iter::once(i..end).collect()))
+            });
+
+        match found {
+            Some((kind, end, ranges)) => {
+                for range in ranges {
+                    let matched: String = chars[range.clone()].iter().collect();
+                    spans.push(ProtectedSpan::new(kind, range.start, range.end, &matched));
+                }
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+    spans
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn at_word_start(chars: &[char], i: usize) -> bool {
+    i == 0 || !is_word_char(chars[i - 1])
+}
+
+fn run_length(chars: &[char], start: usize, predicate: impl Fn(char) -> bool) -> usize {
+    chars[start..].iter().take_while(|&&c| predicate(c)).count()
+}
+
+//バッククォートの連なりをテキスト全体で一度だけ調べておき、閉じる連なりを探し直さない
+struct BacktickRuns {
+    //各位置から続くバッククォートの終わり（バッククォートでなければ None）
+    run_end: Vec<Option<usize>>,
+    //長さごとの連なりの始まり（昇順）
+    starts_by_length: HashMap<usize, Vec<usize>>,
+}
+
+impl BacktickRuns {
+    fn new(chars: &[char]) -> Self {
+        let mut run_end = vec![None; chars.len()];
+        let mut starts_by_length: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut k = 0;
+        while k < chars.len() {
+            let run = run_length(chars, k, |c| c == '`');
+            if run == 0 {
+                k += 1;
+                continue;
+            }
+            run_end[k..k + run].fill(Some(k + run));
+            starts_by_length.entry(run).or_default().push(k);
+            k += run;
+        }
+        BacktickRuns {
+            run_end,
+            starts_by_length,
+        }
+    }
+}
+
+//`code` や ```code``` のように同じ数のバッククォートで閉じる
+fn match_code(backticks: &BacktickRuns, i: usize) -> Option<usize> {
+    let end = backticks.run_end[i]?;
+    let ticks = end - i;
+    let starts = backticks.starts_by_length.get(&ticks)?;
+    let close = starts[starts.partition_point(|&start| start < end)..]
+        .first()
+        .copied()?;
+    Some(close + ticks)
+}
+
+fn match_url(chars: &[char], i: usize) -> Option<usize> {
+    if !at_word_start(chars, i) {
+        return None;
+    }
+    let prefix = URL_PREFIXES.iter().find(|prefix| {
+        prefix.chars().enumerate().all(|(k, p)| {
+            chars
+                .get(i + k)
+                .is_some_and(|c| c.to_ascii_lowercase() == p)
+        })
+    })?;
+
+    let mut end = i + run_length(chars, i, |c| !c.is_whitespace());
+    while end > i && URL_TRAILING_PUNCTUATION.contains(chars[end - 1]) {
+        end -= 1;
+    }
+    (end > i + prefix.len()).then_some(end)
+}
+
+fn match_email(chars: &[char], i: usize) -> Option<usize> {
+    if !at_word_start(chars, i) {
+        return None;
+    }
+    let local = run_length(chars, i, |c| {
+        c.is_ascii_alphanumeric() || "._%+-".contains(c)
+    });
+    if local == 0 || chars.get(i + local) != Some(&'@') {
+        return None;
+    }
+
+    let domain_start = i + local + 1;
+    let mut end = domain_start
+        + run_length(chars, domain_start, |c| {
+            c.is_ascii_alphanumeric() || c == '.' || c == '-'
+        });
+    while end > domain_start && ".-".contains(chars[end - 1]) {
+        end -= 1;
+    }
+    let domain: String = chars[domain_start..end].iter().collect();
+    let valid = !domain.starts_with('.')
+        && domain
+            .rsplit_once('.')
+            .is_some_and(|(host, tld)| !host.is_empty() && tld.len() >= 2);
+    valid.then_some(end)
+}
+
+fn match_mention(chars: &[char], i: usize) -> Option<usize> {
+    if chars[i] != '@' || !at_word_start(chars, i) {
+        return None;
+    }
+    let name = run_length(chars, i + 1, |c| c.is_ascii_alphanumeric() || c == '_');
+    (name > 0).then_some(i + 1 + name)
+}
+
+//数字だけのものはハッシュタグとみなさない
+fn match_hashtag(chars: &[char], i: usize) -> Option<usize> {
+    if chars[i] != '#' || !at_word_start(chars, i) {
+        return None;
+    }
+    let tag = run_length(chars, i + 1, is_word_char);
+    let has_letter = chars[i + 1..i + 1 + tag].iter().any(|c| !c.is_numeric());
+    (tag > 0 && has_letter).then_some(i + 1 + tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds_and_texts(text: &str, kinds: &[EntityKind]) -> Vec<(EntityKind, String)> {
+        find_entities(text, kinds)
+            .into_iter()
+            .map(|span| (span.kind(), span.text().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_all_entities() {
+        let text =
+            "SEE https://example.com/a?b=1, MAIL rikku@albhed.org, @Rikku_99 #アルベド語 `cid = 1`";
+        assert_eq!(
+            kinds_and_texts(text, &EntityKind::ALL),
+            vec![
+                (EntityKind::Url, "https://example.com/a?b=1".to_string()),
+                (EntityKind::Email, "rikku@albhed.org".to_string()),
+                (EntityKind::Mention, "@Rikku_99".to_string()),
+                (EntityKind::Hashtag, "#アルベド語".to_string()),
+                (EntityKind::Code, "`cid = 1`".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_positions_in_chars() {
+        let spans = find_entities("こんにちは @Rikku", &[EntityKind::Mention]);
+        assert_eq!((spans[0].start(), spans[0].end()), (6, 12));
+    }
+
+    #[test]
+    fn test_find_only_enabled_kinds() {
+        let text = "rikku@albhed.org #1 #FFX ``a ` b``";
+        assert_eq!(
+            kinds_and_texts(text, &[EntityKind::Hashtag, EntityKind::Code]),
+            vec![
+                (EntityKind::Hashtag, "#FFX".to_string()),
+                (EntityKind::Code, "``a ` b``".to_string()),
+            ]
+        );
+        assert!(find_entities(text, &[EntityKind::Mention]).is_empty());
+    }
+
+    #[test]
+    fn test_find_placeholders() {
+        let text = "HELLO {name}, %1$d {n, plural, one {# MESSAGE} other {# MESSAGES}}";
+        let texts: Vec<String> = find_entities(text, &[EntityKind::Placeholder])
+            .into_iter()
+            .map(|span| span.text().to_string())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "{name}",
+                "%1$d",
+                "{n, plural, one {",
+                "#",
+                "} other {",
+                "#",
+                "}}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_code_in_backtick_runs() {
+        let texts = |text: &str| -> Vec<String> {
+            find_entities(text, &[EntityKind::Code])
+                .into_iter()
+                .map(|span| span.text().to_string())
+                .collect()
+        };
+        assert_eq!(texts("```a`` b ``` `c`"), vec!["```a`` b ```", "`c`"]);
+        assert_eq!(texts("```a``"), vec!["``a``"]);
+        assert!(texts("``a```").is_empty());
+    }
+
+    #[test]
+    fn test_find_unclosed_code_in_linear_time() {
+        //閉じない連なりが並んでも、開きごとに末尾まで探し直さない
+        for text in ["`a``b".repeat(100_000), "`".repeat(400_000) + "a"] {
+            let started = std::time::Instant::now();
+            find_entities(&text, &[EntityKind::Code]);
+            assert!(started.elapsed().as_secs() < 2);
+        }
+    }
+
+    #[test]
+    fn test_find_code_takes_priority() {
+        let text = "`https://example.com`";
+        assert_eq!(
+            kinds_and_texts(text, &EntityKind::ALL),
+            vec![(EntityKind::Code, text.to_string())]
+        );
+    }
+}
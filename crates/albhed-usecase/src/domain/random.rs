@@ -0,0 +1,70 @@
+//シードから同じ結果を再現できるよう、依存のない小さな乱数生成器（SplitMix64）を使う
+pub struct SeededRandom(u64);
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        SeededRandom(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    //0以上1未満
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    //0以上bound未満。boundが0なら0
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound.max(1) as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    //出現回数で重み付けして選ぶ
+    pub fn pick_weighted<'a>(&mut self, weighted: &'a [(String, usize)]) -> Option<&'a str> {
+        let total: usize = weighted.iter().map(|(_, count)| count).sum();
+        let mut target = self.below(total);
+        for (item, count) in weighted {
+            if target < *count {
+                return Some(item);
+            }
+            target -= count;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_random_is_reproducible() {
+        let mut first = SeededRandom::new(42);
+        let mut second = SeededRandom::new(42);
+        let a: Vec<u64> = (0..5).map(|_| first.next_u64()).collect();
+        let b: Vec<u64> = (0..5).map(|_| second.next_u64()).collect();
+        assert_eq!(a, b);
+
+        let mut letters: Vec<char> = ('A'..='Z').collect();
+        first.shuffle(&mut letters);
+        letters.sort();
+        assert_eq!(letters, ('A'..='Z').collect::<Vec<char>>());
+        assert!((0..100).all(|_| (0.0..1.0).contains(&first.next_f64())));
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashSet;
+
+use crate::domain::entities::{AlBhedText, OriginalText};
+use crate::domain::vocabulary::ENGLISH_WORDS;
+
+const MAX_DISTANCE: usize = 2;
+const MAX_SUGGESTIONS: usize = 5;
+
+pub struct Suggestion {
+    text: String,
+    decoded: String,
+    distance: usize,
+}
+
+impl Suggestion {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn decoded(&self) -> &str {
+        &self.decoded
+    }
+
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+}
+
+pub struct Misspelling {
+    start: usize,
+    end: usize,
+    text: String,
+    decoded: String,
+    suggestions: Vec<Suggestion>,
+}
+
+impl Misspelling {
+    //入力テキスト中の文字（char）単位の位置
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn decoded(&self) -> &str {
+        &self.decoded
+    }
+
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+}
+
+pub struct SpellChecker {
+    known_words: HashSet<String>,
+    //(暗号文, 平文) の組
+    entries: Vec<(String, String)>,
+}
+
+impl SpellChecker {
+    pub fn new() -> Self {
+        Self::with_vocabulary(ENGLISH_WORDS)
+    }
+
+    pub fn with_vocabulary(words: &[&str]) -> Self {
+        let mut known_words = HashSet::new();
+        let mut entries = Vec::new();
+        for word in words {
+            let plain = word.to_uppercase();
+            if plain.is_empty() || !known_words.insert(plain.clone()) {
+                continue;
+            }
+            let cipher = OriginalText::new(&plain)
+                .unwrap()
+                .encode()
+                .text()
+                .to_string();
+            entries.push((cipher, plain));
+        }
+
+        SpellChecker {
+            known_words,
+            entries,
+        }
+    }
+
+    pub fn check(&self, text: &AlBhedText) -> Vec<Misspelling> {
+        latin_tokens(text.text())
+            .into_iter()
+            .filter_map(|(start, token)| {
+                let cipher = token.to_uppercase();
+                let decoded = AlBhedText::new(&cipher)
+                    .unwrap()
+                    .decode()
+                    .text()
+                    .to_string();
+                if self.known_words.contains(&decoded) {
+                    return None;
+                }
+
+                Some(Misspelling {
+                    start,
+                    end: start + token.chars().count(),
+                    suggestions: self.suggest(&token, &cipher),
+                    text: token,
+                    decoded,
+                })
+            })
+            .collect()
+    }
+
+    fn suggest(&self, token: &str, cipher: &str) -> Vec<Suggestion> {
+        let token_chars: Vec<char> = cipher.chars().collect();
+        let mut candidates: Vec<(usize, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(rank, (entry_cipher, _))| {
+                let entry_chars: Vec<char> = entry_cipher.chars().collect();
+                if entry_chars.len().abs_diff(token_chars.len()) > MAX_DISTANCE {
+                    return None;
+                }
+                let distance = edit_distance(&token_chars, &entry_chars);
+                (distance <= MAX_DISTANCE).then_some((distance, rank))
+            })
+            .collect();
+        candidates.sort();
+
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(distance, rank)| {
+                let (entry_cipher, entry_plain) = &self.entries[rank];
+                Suggestion {
+                    text: match_case(token, entry_cipher),
+                    decoded: match_case(token, entry_plain),
+                    distance,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for SpellChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//ASCII英字の連続をトークンとして (開始位置, トークン) を返す
+fn latin_tokens(text: &str) -> Vec<(usize, String)> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    for (index, c) in text.chars().enumerate() {
+        if c.is_ascii_alphabetic() {
+            if current.is_empty() {
+                start = index;
+            }
+            current.push(c);
+        } else if !current.is_empty() {
+            tokens.push((start, std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((start, current));
+    }
+    tokens
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+//元のトークンの大文字・小文字の形に合わせる（語彙はASCIIのみ）
+fn match_case(token: &str, word: &str) -> String {
+    let capitalized = token.len() > 1
+        && token.starts_with(|c: char| c.is_ascii_uppercase())
+        && token[1..].chars().all(|c| c.is_ascii_lowercase());
+
+    if token.chars().all(|c| c.is_ascii_lowercase()) {
+        word.to_lowercase()
+    } else if capitalized {
+        let lower = word.to_lowercase();
+        lower[..1].to_uppercase() + &lower[1..]
+    } else {
+        word.to_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_check_known_words() {
+        let checker = SpellChecker::new();
+        let text = AlBhedText::new("FRYD EC DRYD?").unwrap();
+        assert!(checker.check(&text).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_check_suggests_correction() {
+        let checker = SpellChecker::new();
+        // "FRYD EC DRYT?" -> "WHAT IS THAD?"
+        let text = AlBhedText::new("FRYD EC DRYT?").unwrap();
+        let result = checker.check(&text);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].start(), 8);
+        assert_eq!(result[0].end(), 12);
+        assert_eq!(result[0].decoded(), "THAD");
+        assert_eq!(result[0].suggestions()[0].text(), "DRYD");
+        assert_eq!(result[0].suggestions()[0].decoded(), "THAT");
+        assert_eq!(result[0].suggestions()[0].distance(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_check_keeps_token_case() {
+        let checker = SpellChecker::with_vocabulary(&["that"]);
+        let text = AlBhedText::new("Dryt").unwrap();
+        let result = checker.check(&text);
+
+        assert_eq!(result[0].suggestions()[0].text(), "Dryd");
+        assert_eq!(result[0].suggestions()[0].decoded(), "That");
+    }
+
+    #[test]
+    fn test_check_ignores_kana() {
+        let checker = SpellChecker::new();
+        let text = AlBhedText::new("ギアンダメネ！").unwrap();
+        assert!(checker.check(&text).is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let a: Vec<char> = "KITTEN".chars().collect();
+        let b: Vec<char> = "SITTING".chars().collect();
+        assert_eq!(edit_distance(&a, &b), 3);
+    }
+}
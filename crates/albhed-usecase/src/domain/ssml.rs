@@ -0,0 +1,226 @@
+use std::ops::Range;
+
+use crate::domain::pronunciation::pronounce;
+
+const SSML_NAMESPACE: &str = "http://www.w3.org/2001/10/synthesis";
+//アルベド語は登録された言語タグがないので私用タグを使う
+const ALBHED_LANGUAGE: &str = "art-x-albhed";
+const SENTENCE_ENDINGS: &str = ".!?。！？";
+const CLAUSE_ENDINGS: &str = ",;:、";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SsmlOptions {
+    language: String,
+    hints: bool,
+}
+
+impl SsmlOptions {
+    //language は保護した平文を読む言語（例: en-US）
+    pub fn new(language: &str) -> Self {
+        SsmlOptions {
+            language: language.to_string(),
+            hints: false,
+        }
+    }
+
+    //句読点から break と prosody を付ける
+    pub fn with_hints(mut self) -> Self {
+        self.hints = true;
+        self
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn hints(&self) -> bool {
+        self.hints
+    }
+}
+
+enum Segment {
+    Text(char),
+    Phoneme(String, String),
+    Lang(String),
+}
+
+//暗号文の単語には phoneme でIPAを、保護した平文には lang を付けたSSML 1.1の文書を作る
+//protected は出力での文字位置
+pub fn render_ssml(text: &str, protected: &[Range<usize>], options: &SsmlOptions) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let words = pronounce(text);
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(range) = protected
+            .iter()
+            .find(|range| range.start == i && i < range.end && range.end <= chars.len())
+        {
+            segments.push(Segment::Lang(chars[range.clone()].iter().collect()));
+            i = range.end;
+        } else if let Some(word) = words.iter().find(|word| word.start() == i) {
+            segments.push(Segment::Phoneme(
+                word.word().to_string(),
+                word.ipa().to_string(),
+            ));
+            i = word.end();
+        } else {
+            segments.push(Segment::Text(chars[i]));
+            i += 1;
+        }
+    }
+
+    let mut body = String::new();
+    let mut sentence = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Text(c) => {
+                sentence.push_str(&escape(&c.to_string()));
+                if options.hints() && CLAUSE_ENDINGS.contains(c) {
+                    sentence.push_str("<break strength=\"weak\"/>");
+                }
+                if options.hints() && SENTENCE_ENDINGS.contains(c) {
+                    body.push_str(&with_prosody(&sentence, c));
+                    body.push_str("<break strength=\"strong\"/>");
+                    sentence.clear();
+                }
+            }
+            Segment::Phoneme(word, ipa) => sentence.push_str(&format!(
+                "<phoneme alphabet=\"ipa\" ph=\"{}\">{}</phoneme>",
+                escape(&ipa),
+                escape(&word)
+            )),
+            Segment::Lang(plain) => sentence.push_str(&format!(
+                "<lang xml:lang=\"{}\">{}</lang>",
+                escape(options.language()),
+                escape(&plain)
+            )),
+        }
+    }
+    body.push_str(&sentence);
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+         <speak version=\"1.1\" xmlns=\"{}\" xml:lang=\"{}\">{}</speak>",
+        SSML_NAMESPACE, ALBHED_LANGUAGE, body
+    )
+}
+
+//疑問文は語尾を上げ、感嘆文は強めに読む
+fn with_prosody(sentence: &str, ending: char) -> String {
+    match ending {
+        '?' | '？' => format!("<prosody pitch=\"+10%\">{}</prosody>", sentence),
+        '!' | '！' => format!("<prosody volume=\"loud\">{}</prosody>", sentence),
+        _ => sentence.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    //tests/ssml の SSML 1.1 スキーマで xmllint に検証させる
+    fn validate(document: &str) {
+        let schema = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/ssml/synthesis.xsd");
+        let mut xmllint = Command::new("xmllint")
+            .args(["--noout", "--nonet", "--schema", schema, "-"])
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("xmllint is required to validate SSML");
+        xmllint
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(document.as_bytes())
+            .unwrap();
+        let output = xmllint.wait_with_output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}\n{}",
+            document,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_ssml() {
+        let invalid = [
+            "<speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\"/>",
+            "<speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"en\">\
+             <break strength=\"loud\"/></speak>",
+            "<speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"en\">\
+             <phoneme alphabet=\"ipa\">a</phoneme></speak>",
+        ];
+        for document in invalid {
+            assert!(std::panic::catch_unwind(|| validate(document)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_render_ssml() {
+        let text = "Fryd ec @Rikku?";
+        let document = render_ssml(
+            text,
+            std::slice::from_ref(&(8..14)),
+            &SsmlOptions::new("en-US"),
+        );
+        validate(&document);
+        assert_eq!(
+            document,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"art-x-albhed\">\
+             <phoneme alphabet=\"ipa\" ph=\"fɹɪd\">Fryd</phoneme> \
+             <phoneme alphabet=\"ipa\" ph=\"ek\">ec</phoneme> \
+             <lang xml:lang=\"en-US\">@Rikku</lang>?</speak>"
+        );
+    }
+
+    #[test]
+    fn test_render_ssml_with_hints() {
+        let text = "RE, ZA! Fryd? <a & b>";
+        let document = render_ssml(text, &[], &SsmlOptions::new("en-US").with_hints());
+        validate(&document);
+        assert!(document.contains(
+            "<prosody volume=\"loud\"><phoneme alphabet=\"ipa\" ph=\"ɹe\">RE</phoneme>,\
+             <break strength=\"weak\"/> <phoneme alphabet=\"ipa\" ph=\"za\">ZA</phoneme>!</prosody>\
+             <break strength=\"strong\"/>"
+        ));
+        assert!(document.contains("<prosody pitch=\"+10%\"> <phoneme"));
+        assert!(document.contains("&lt;<phoneme alphabet=\"ipa\" ph=\"a\">a</phoneme> &amp; "));
+    }
+
+    #[test]
+    fn test_render_ssml_tags_only_given_offsets() {
+        //同じ文字列が前にあっても、渡した位置だけを平文として読む
+        let text = "@Rikku @Rikku";
+        let document = render_ssml(
+            text,
+            std::slice::from_ref(&(7..13)),
+            &SsmlOptions::new("en-US"),
+        );
+        validate(&document);
+        assert!(document.ends_with(
+            "@<phoneme alphabet=\"ipa\" ph=\"ˈɹik.ku\">Rikku</phoneme> \
+             <lang xml:lang=\"en-US\">@Rikku</lang></speak>"
+        ));
+    }
+}
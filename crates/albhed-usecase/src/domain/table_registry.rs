@@ -0,0 +1,378 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::domain::cipher_plugin::CipherPlugin;
+use crate::domain::entities::{Language, SubstitutionTable};
+use crate::domain::error::DomainError;
+
+pub const DEFAULT_TABLE: &str = "albhed";
+//合言葉から作った表をいくつまで持っておくか
+pub const DEFAULT_KEYED_LIMIT: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableKind {
+    //最初から登録されている表
+    Builtin,
+    //合言葉から作った表
+    Keyed,
+    //読み込んだプラグイン
+    Plugin,
+    //利用者が名前を付けて登録した表
+    Custom,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableInfo {
+    name: String,
+    kind: TableKind,
+    description: String,
+    languages: Vec<Language>,
+    size: usize,
+}
+
+impl TableInfo {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> TableKind {
+        self.kind
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    //変換ルールの数
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+enum Cipher {
+    Table(Arc<SubstitutionTable>),
+    Plugin(Arc<dyn CipherPlugin>),
+}
+
+struct RegisteredTable {
+    kind: TableKind,
+    description: String,
+    cipher: Cipher,
+    //最後に使われた時刻。合言葉の表を捨てる順番に使う
+    last_used: AtomicU64,
+}
+
+pub struct TableRegistry {
+    tables: RwLock<HashMap<String, RegisteredTable>>,
+    keyed_limit: usize,
+    clock: AtomicU64,
+}
+
+impl TableRegistry {
+    pub fn new() -> Self {
+        let registry = TableRegistry {
+            tables: RwLock::new(HashMap::new()),
+            keyed_limit: DEFAULT_KEYED_LIMIT,
+            clock: AtomicU64::new(0),
+        };
+        registry
+            .register(
+                DEFAULT_TABLE,
+                TableKind::Builtin,
+                "Al Bhed from Final Fantasy X",
+                SubstitutionTable::albhed().clone(),
+            )
+            .expect("registry starts empty");
+        registry
+    }
+
+    //上限を超えたら、いちばん長く使われていない合言葉の表から捨てる
+    pub fn with_keyed_limit(mut self, limit: usize) -> Self {
+        self.keyed_limit = limit.max(1);
+        self
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub fn register(
+        &self,
+        name: &str,
+        kind: TableKind,
+        description: &str,
+        table: SubstitutionTable,
+    ) -> Result<Arc<SubstitutionTable>, DomainError> {
+        let table = Arc::new(table);
+        self.insert(name, kind, description, Cipher::Table(table.clone()))?;
+        Ok(table)
+    }
+
+    //同じ合言葉からは同じ表ができるので、登録済みならそれを返す
+    pub fn register_keyed(
+        &self,
+        hash: &str,
+        table: SubstitutionTable,
+    ) -> Result<Arc<SubstitutionTable>, DomainError> {
+        let mut tables = self.tables.write().unwrap();
+        match tables.get(hash) {
+            Some(RegisteredTable {
+                kind: TableKind::Keyed,
+                cipher: Cipher::Table(existing),
+                last_used,
+                ..
+            }) => {
+                last_used.store(self.tick(), Ordering::Relaxed);
+                return Ok(existing.clone());
+            }
+            Some(_) => return Err(DomainError::DuplicateTableName(hash.to_string())),
+            None => {}
+        }
+
+        let keyed: Vec<(&String, u64)> = tables
+            .iter()
+            .filter(|(_, registered)| registered.kind == TableKind::Keyed)
+            .map(|(name, registered)| (name, registered.last_used.load(Ordering::Relaxed)))
+            .collect();
+        if keyed.len() >= self.keyed_limit {
+            if let Some(oldest) = keyed
+                .into_iter()
+                .min_by_key(|&(_, last_used)| last_used)
+                .map(|(name, _)| name.clone())
+            {
+                tables.remove(&oldest);
+            }
+        }
+
+        let table = Arc::new(table);
+        tables.insert(
+            hash.to_string(),
+            RegisteredTable {
+                kind: TableKind::Keyed,
+                description: "Keyed table".to_string(),
+                cipher: Cipher::Table(table.clone()),
+                last_used: AtomicU64::new(self.tick()),
+            },
+        );
+        Ok(table)
+    }
+
+    pub fn register_plugin(
+        &self,
+        name: &str,
+        description: &str,
+        plugin: Arc<dyn CipherPlugin>,
+    ) -> Result<(), DomainError> {
+        self.insert(name, TableKind::Plugin, description, Cipher::Plugin(plugin))
+    }
+
+    //登録済みの名前は上書きしない
+    fn insert(
+        &self,
+        name: &str,
+        kind: TableKind,
+        description: &str,
+        cipher: Cipher,
+    ) -> Result<(), DomainError> {
+        match self.tables.write().unwrap().entry(name.to_string()) {
+            Entry::Occupied(_) => Err(DomainError::DuplicateTableName(name.to_string())),
+            Entry::Vacant(entry) => {
+                entry.insert(RegisteredTable {
+                    kind,
+                    description: description.to_string(),
+                    cipher,
+                    last_used: AtomicU64::new(self.tick()),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    //プラグインの名前では None
+    pub fn get(&self, name: &str) -> Option<Arc<SubstitutionTable>> {
+        let tables = self.tables.read().unwrap();
+        let registered = tables.get(name)?;
+        registered.last_used.store(self.tick(), Ordering::Relaxed);
+        match &registered.cipher {
+            Cipher::Table(table) => Some(table.clone()),
+            Cipher::Plugin(_) => None,
+        }
+    }
+
+    pub fn plugin(&self, name: &str) -> Option<Arc<dyn CipherPlugin>> {
+        match &self.tables.read().unwrap().get(name)?.cipher {
+            Cipher::Table(_) => None,
+            Cipher::Plugin(plugin) => Some(plugin.clone()),
+        }
+    }
+
+    pub fn info(&self, name: &str) -> Option<TableInfo> {
+        self.tables
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|registered| to_info(name, registered))
+    }
+
+    //名前順
+    //合言葉から作った表は、ハッシュを知っている人だけが使えるよう一覧に出さない
+    pub fn list(&self) -> Vec<TableInfo> {
+        let mut infos: Vec<TableInfo> = self
+            .tables
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, registered)| registered.kind != TableKind::Keyed)
+            .map(|(name, registered)| to_info(name, registered))
+            .collect();
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+        infos
+    }
+}
+
+impl Default for TableRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_info(name: &str, registered: &RegisteredTable) -> TableInfo {
+    let (languages, size) = match &registered.cipher {
+        Cipher::Table(table) => (table.languages(), table.pairs().len()),
+        Cipher::Plugin(_) => (Vec::new(), 0),
+    };
+    TableInfo {
+        name: name.to_string(),
+        kind: registered.kind,
+        description: registered.description.clone(),
+        languages,
+        size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::cipher_plugin::test_plugins::Reverse;
+
+    #[test]
+    fn test_register_and_get() {
+        let registry = TableRegistry::new();
+        registry
+            .register(
+                "copy",
+                TableKind::Builtin,
+                "",
+                SubstitutionTable::albhed().clone(),
+            )
+            .unwrap();
+
+        assert!(registry.get("copy").is_some());
+        assert!(registry.get("unknown").is_none());
+    }
+
+    #[test]
+    fn test_register_duplicate_name() {
+        let registry = TableRegistry::new();
+        let result = registry.register(
+            DEFAULT_TABLE,
+            TableKind::Builtin,
+            "",
+            SubstitutionTable::albhed().restricted_to(&[]),
+        );
+
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Table albhed is already registered"
+        );
+        assert_eq!(
+            registry.get(DEFAULT_TABLE).unwrap().pairs(),
+            SubstitutionTable::albhed().pairs()
+        );
+        assert!(registry
+            .register_plugin(DEFAULT_TABLE, "", Arc::new(Reverse))
+            .is_err());
+        assert!(registry.plugin(DEFAULT_TABLE).is_none());
+    }
+
+    #[test]
+    fn test_keyed_tables_are_not_listed() {
+        let registry = TableRegistry::new();
+        let first = registry
+            .register_keyed("0123456789abcdef", SubstitutionTable::albhed().clone())
+            .unwrap();
+        let second = registry
+            .register_keyed("0123456789abcdef", SubstitutionTable::albhed().clone())
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(
+            registry.info("0123456789abcdef").unwrap().kind(),
+            TableKind::Keyed
+        );
+        let names: Vec<String> = registry
+            .list()
+            .iter()
+            .map(|info| info.name().to_string())
+            .collect();
+        assert_eq!(names, vec![DEFAULT_TABLE]);
+        assert!(registry
+            .register_keyed(DEFAULT_TABLE, SubstitutionTable::albhed().clone())
+            .is_err());
+    }
+
+    #[test]
+    fn test_keyed_limit_evicts_least_recently_used() {
+        let registry = TableRegistry::new().with_keyed_limit(2);
+        let table = || SubstitutionTable::albhed().clone();
+        registry.register_keyed("first", table()).unwrap();
+        registry.register_keyed("second", table()).unwrap();
+        registry.get("first").unwrap();
+        registry.register_keyed("third", table()).unwrap();
+
+        assert!(registry.get("first").is_some());
+        assert!(registry.get("second").is_none());
+        assert!(registry.get("third").is_some());
+        //組み込みの表は数に入らず、捨てられない
+        assert!(registry.get(DEFAULT_TABLE).is_some());
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_builtin_tables() {
+        let registry = TableRegistry::new();
+        let infos = registry.list();
+
+        assert_eq!(infos.len(), 1);
+        assert_eq!(infos[0].name(), DEFAULT_TABLE);
+        assert_eq!(infos[0].kind(), TableKind::Builtin);
+        assert_eq!(infos[0].languages(), Language::ALL);
+        assert_eq!(
+            registry.info(DEFAULT_TABLE).unwrap().size(),
+            SubstitutionTable::albhed().pairs().len()
+        );
+    }
+
+    #[test]
+    fn test_register_plugin() {
+        let registry = TableRegistry::new();
+        registry
+            .register_plugin("reverse", "Reversed text", Arc::new(Reverse))
+            .unwrap();
+
+        assert!(registry.get("reverse").is_none());
+        assert!(registry.plugin(DEFAULT_TABLE).is_none());
+        assert_eq!(
+            registry.plugin("reverse").unwrap().encode("AB").unwrap(),
+            "BA"
+        );
+        let info = registry.info("reverse").unwrap();
+        assert_eq!((info.kind(), info.size()), (TableKind::Plugin, 0));
+    }
+}
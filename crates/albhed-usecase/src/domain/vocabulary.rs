@@ -0,0 +1,46 @@
+//スペルチェック用の英単語リスト（大文字、おおよそ頻度順）
+#[rustfmt::skip]
+pub static ENGLISH_WORDS: &[&str] = &[
+    "THE", "BE", "TO", "OF", "AND", "A", "IN", "THAT", "HAVE", "I", "IT", "FOR", "NOT", "ON",
+    "WITH", "HE", "AS", "YOU", "DO", "AT", "THIS", "BUT", "HIS", "BY", "FROM", "THEY", "WE", "SAY",
+    "HER", "SHE", "OR", "AN", "WILL", "MY", "ONE", "ALL", "WOULD", "THERE", "THEIR", "WHAT", "SO",
+    "UP", "OUT", "IF", "ABOUT", "WHO", "GET", "WHICH", "GO", "ME", "WHEN", "MAKE", "CAN", "LIKE",
+    "TIME", "NO", "JUST", "HIM", "KNOW", "TAKE", "PEOPLE", "INTO", "YEAR", "YOUR", "GOOD", "SOME",
+    "COULD", "THEM", "SEE", "OTHER", "THAN", "THEN", "NOW", "LOOK", "ONLY", "COME", "ITS", "OVER",
+    "THINK", "ALSO", "BACK", "AFTER", "USE", "TWO", "HOW", "OUR", "WORK", "FIRST", "WELL", "WAY",
+    "EVEN", "NEW", "WANT", "BECAUSE", "ANY", "THESE", "GIVE", "DAY", "MOST", "US", "IS", "ARE",
+    "WAS", "WERE", "AM", "BEEN", "HAS", "HAD", "DID", "DOES", "DONE", "SAID", "GOT", "WENT", "GONE",
+    "CAME", "MADE", "KNEW", "TOOK", "SAW", "SEEN", "HERE", "WHERE", "WHY", "YES", "OK", "OKAY",
+    "HEY", "HELLO", "HI", "BYE", "THANKS", "THANK", "PLEASE", "SORRY", "STOP", "WAIT", "HELP",
+    "RUN", "HURRY", "LET", "LETS", "MUST", "SHOULD", "MAY", "MIGHT", "SHALL", "NEED", "TELL", "ASK",
+    "CALL", "TRY", "FIND", "LEAVE", "KEEP", "FEEL", "SEEM", "TURN", "START", "SHOW", "HEAR", "PLAY",
+    "MOVE", "LIVE", "BELIEVE", "BRING", "HAPPEN", "WRITE", "SIT", "STAND", "LOSE", "PAY", "MEET",
+    "LEARN", "CHANGE", "LEAD", "UNDERSTAND", "WATCH", "FOLLOW", "CREATE", "SPEAK", "READ", "SPEND",
+    "GROW", "OPEN", "WALK", "WIN", "OFFER", "REMEMBER", "LOVE", "CONSIDER", "APPEAR", "BUY",
+    "SERVE", "DIE", "SEND", "BUILD", "STAY", "FALL", "CUT", "REACH", "KILL", "RAISE", "PASS",
+    "SELL", "DECIDE", "RETURN", "EXPLAIN", "HOPE", "FIGHT", "PROTECT", "SAVE", "ESCAPE", "ATTACK",
+    "DEFEAT", "DESTROY", "FLY", "SWIM", "JUMP", "CLOSE", "END", "BEGIN", "FINISH", "LIFE", "MAN",
+    "WOMAN", "CHILD", "WORLD", "HAND", "PART", "PLACE", "CASE", "WEEK", "THING", "POINT", "HOME",
+    "WATER", "ROOM", "MOTHER", "FATHER", "BROTHER", "SISTER", "FRIEND", "FRIENDS", "AREA", "MONEY",
+    "STORY", "FACT", "MONTH", "LOT", "RIGHT", "STUDY", "BOOK", "EYE", "EYES", "JOB", "WORD",
+    "WORDS", "BUSINESS", "ISSUE", "SIDE", "KIND", "HEAD", "HOUSE", "FRIENDSHIP", "POWER", "HOUR",
+    "GAME", "LINE", "NIGHT", "MORNING", "NAME", "SHIP", "AIRSHIP", "MACHINE", "MACHINA", "TEMPLE",
+    "SUMMONER", "GUARDIAN", "SIN", "SPIRA", "DESERT", "SAND", "SEA", "SKY", "FIRE", "ICE",
+    "THUNDER", "WIND", "LIGHT", "DARK", "SWORD", "GUN", "BOMB", "ENEMY", "MONSTER", "FIEND",
+    "FIENDS", "TEAM", "LANGUAGE", "PRIMER", "SECRET", "MESSAGE", "TRUTH", "DREAM", "JOURNEY",
+    "PATH", "ROAD", "GREAT", "LITTLE", "OWN", "OLD", "BIG", "HIGH", "DIFFERENT", "SMALL", "LARGE",
+    "NEXT", "EARLY", "YOUNG", "IMPORTANT", "FEW", "BAD", "SAME", "ABLE", "LATE", "HARD", "REAL",
+    "BEST", "BETTER", "SURE", "FREE", "TRUE", "WHOLE", "STRONG", "WEAK", "FAST", "SLOW", "HOT",
+    "COLD", "SAFE", "DEAD", "ALIVE", "READY", "HAPPY", "SAD", "ANGRY", "AFRAID", "STRANGE", "FUNNY",
+    "CRAZY", "LONG", "SHORT", "LAST", "FULL", "EMPTY", "VERY", "TOO", "STILL", "AGAIN", "NEVER",
+    "ALWAYS", "SOMETIMES", "OFTEN", "ALREADY", "SOON", "TODAY", "TOMORROW", "YESTERDAY", "AWAY",
+    "DOWN", "OFF", "THROUGH", "BEFORE", "UNDER", "AROUND", "BETWEEN", "AGAINST", "WITHOUT",
+    "WITHIN", "ALONG", "BEHIND", "INSIDE", "OUTSIDE", "NOTHING", "SOMETHING", "EVERYTHING",
+    "ANYTHING", "SOMEONE", "EVERYONE", "ANYONE", "NOBODY", "MYSELF", "YOURSELF", "HIMSELF",
+    "HERSELF", "ITSELF", "OURSELVES", "THEMSELVES", "EACH", "EVERY", "BOTH", "EITHER", "NEITHER",
+    "MANY", "MUCH", "MORE", "LESS", "ENOUGH", "SUCH", "WHILE", "UNTIL", "SINCE", "THOUGH",
+    "ALTHOUGH", "UNLESS", "WHETHER", "ONCE", "THREE", "FOUR", "FIVE", "SIX", "SEVEN", "EIGHT",
+    "NINE", "TEN", "HUNDRED", "THOUSAND", "IM", "YOURE", "DONT", "CANT", "WONT", "ISNT", "ARENT",
+    "WASNT", "DIDNT", "DOESNT", "HAVENT", "ILL", "IVE", "ID", "THEYRE", "THATS", "WHATS", "THERES",
+    "HERES", "GOING", "DOING", "COMING", "LOOKING", "TRYING", "WAITING",
+];
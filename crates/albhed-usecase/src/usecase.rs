@@ -0,0 +1,10 @@
+pub mod cipher_usecase;
+pub mod corpus_usecase;
+pub mod decode_usecase;
+pub mod encode_usecase;
+pub mod error;
+pub mod input_lint;
+pub mod name_usecase;
+pub mod spellcheck_usecase;
+pub mod table_usecase;
+pub mod translate_usecase;
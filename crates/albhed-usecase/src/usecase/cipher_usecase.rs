@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use crate::domain::entities::Script;
+use crate::domain::table_registry::{TableInfo, TableRegistry};
+use crate::usecase::error::UsecaseError;
+
+pub trait CipherInputPort {
+    fn list(&self) -> Result<CipherListOutputData, UsecaseError>;
+    fn alphabet(
+        &self,
+        input_data: CipherAlphabetInputData,
+    ) -> Result<CipherAlphabetOutputData, UsecaseError>;
+}
+
+pub struct CipherListOutputData {
+    tables: Vec<TableInfo>,
+}
+
+pub struct CipherAlphabetInputData {
+    name: String,
+}
+
+pub struct CipherAlphabetOutputData {
+    name: String,
+    //(文字の種類, (平文, アルベド語) の組)
+    alphabets: Vec<(Script, Vec<(char, char)>)>,
+}
+
+impl CipherListOutputData {
+    pub fn new(tables: Vec<TableInfo>) -> Self {
+        CipherListOutputData { tables }
+    }
+
+    pub fn get_tables(&self) -> &[TableInfo] {
+        &self.tables
+    }
+}
+
+impl CipherAlphabetInputData {
+    pub fn new(name: &str) -> Self {
+        CipherAlphabetInputData {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl CipherAlphabetOutputData {
+    pub fn new(name: &str, alphabets: Vec<(Script, Vec<(char, char)>)>) -> Self {
+        CipherAlphabetOutputData {
+            name: name.to_string(),
+            alphabets,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_alphabets(&self) -> &[(Script, Vec<(char, char)>)] {
+        &self.alphabets
+    }
+}
+
+pub struct CipherInteractor {
+    registry: Arc<TableRegistry>,
+}
+
+impl CipherInteractor {
+    pub fn new() -> CipherInteractor {
+        Self::with_registry(Arc::new(TableRegistry::new()))
+    }
+
+    pub fn with_registry(registry: Arc<TableRegistry>) -> CipherInteractor {
+        CipherInteractor { registry }
+    }
+}
+
+impl Default for CipherInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CipherInputPort for CipherInteractor {
+    fn list(&self) -> Result<CipherListOutputData, UsecaseError> {
+        Ok(CipherListOutputData::new(self.registry.list()))
+    }
+
+    fn alphabet(
+        &self,
+        input_data: CipherAlphabetInputData,
+    ) -> Result<CipherAlphabetOutputData, UsecaseError> {
+        let name = input_data.get_name();
+        if self.registry.plugin(name).is_some() {
+            return Err(UsecaseError::NoAlphabet(name.to_string()));
+        }
+        let table = self
+            .registry
+            .get(name)
+            .ok_or_else(|| UsecaseError::UnknownTable(name.to_string()))?;
+        let alphabets = table
+            .languages()
+            .into_iter()
+            .map(|language| (language.script(), table.pairs_for(language)))
+            .collect();
+        Ok(CipherAlphabetOutputData::new(name, alphabets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::table_registry::DEFAULT_TABLE;
+
+    #[test]
+    fn test_list() {
+        let cipher_port = CipherInteractor::new();
+        let result = cipher_port.list();
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get_tables()[0].name(), DEFAULT_TABLE);
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_alphabet() {
+        let cipher_port = CipherInteractor::new();
+        let result = cipher_port.alphabet(CipherAlphabetInputData::new(DEFAULT_TABLE));
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        let scripts: Vec<Script> = output.get_alphabets().iter().map(|(s, _)| *s).collect();
+        assert_eq!(scripts, vec![Script::Latin, Script::Kana]);
+        assert_eq!(output.get_alphabets()[0].1.len(), 26);
+        assert_eq!(output.get_alphabets()[0].1[0], ('A', 'Y'));
+    }
+
+    #[test]
+    fn test_alphabet_unknown_table() {
+        let cipher_port = CipherInteractor::new();
+        let result = cipher_port.alphabet(CipherAlphabetInputData::new("unknown"));
+
+        assert_eq!(result.err().unwrap().to_string(), "Unknown table: unknown");
+    }
+}
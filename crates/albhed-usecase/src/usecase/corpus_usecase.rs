@@ -0,0 +1,441 @@
+use std::ops::Range;
+
+use crate::domain::random::SeededRandom;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::translate_usecase::{
+    CaseMode, Direction, TranslateInputData, TranslateInputPort, TranslateInteractor,
+    TranslationOptions,
+};
+
+//名前を差し込むときに使う、変換しない固有名詞
+const DEFAULT_NAMES: [&str; 7] = [
+    "TIDUS", "YUNA", "AURON", "WAKKA", "LULU", "KIMAHRI", "RIKKU",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Split {
+    Train,
+    Dev,
+    Test,
+}
+
+impl Split {
+    pub const ALL: [Split; 3] = [Split::Train, Split::Dev, Split::Test];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Split::Train => "train",
+            Split::Dev => "dev",
+            Split::Test => "test",
+        }
+    }
+}
+
+pub struct ParallelPair {
+    plaintext: String,
+    ciphertext: String,
+    split: Split,
+    primers: Vec<char>,
+    names: Vec<String>,
+}
+
+impl ParallelPair {
+    pub fn plaintext(&self) -> &str {
+        &self.plaintext
+    }
+
+    pub fn ciphertext(&self) -> &str {
+        &self.ciphertext
+    }
+
+    pub fn split(&self) -> Split {
+        self.split
+    }
+
+    //変換せずに残した文字
+    pub fn primers(&self) -> &[char] {
+        &self.primers
+    }
+
+    //差し込んだ名前
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+pub trait CorpusInputPort {
+    fn generate(&self, input_data: CorpusInputData) -> Result<CorpusOutputData, UsecaseError>;
+}
+
+pub struct CorpusInputData {
+    texts: Vec<String>,
+    seed: u64,
+    primer_rate: f64,
+    case_noise: f64,
+    names: Vec<String>,
+    name_rate: f64,
+    split: [f64; 3],
+}
+
+pub struct CorpusOutputData {
+    pairs: Vec<ParallelPair>,
+}
+
+impl CorpusInputData {
+    pub fn new(texts: Vec<String>, seed: u64) -> Self {
+        CorpusInputData {
+            texts,
+            seed,
+            primer_rate: 0.0,
+            case_noise: 0.0,
+            names: DEFAULT_NAMES.iter().map(|name| name.to_string()).collect(),
+            name_rate: 0.0,
+            split: [0.8, 0.1, 0.1],
+        }
+    }
+
+    //この割合の文で、ランダムに選んだ文字を覚えたことにして変換せずに残す
+    pub fn with_primer_rate(mut self, rate: f64) -> Self {
+        self.primer_rate = rate;
+        self
+    }
+
+    //この割合の文字を、平文と暗号文の両方で小文字にする
+    pub fn with_case_noise(mut self, rate: f64) -> Self {
+        self.case_noise = rate;
+        self
+    }
+
+    //この割合の文に、変換しない名前を差し込む
+    pub fn with_names(mut self, names: &[String], rate: f64) -> Self {
+        if !names.is_empty() {
+            self.names = names.to_vec();
+        }
+        self.name_rate = rate;
+        self
+    }
+
+    //(train, dev, test) の割合。文をシャッフルし、この割合の文数で切り分ける
+    pub fn with_split(mut self, train: f64, dev: f64, test: f64) -> Self {
+        self.split = [train, dev, test];
+        self
+    }
+
+    pub fn get_texts(&self) -> &[String] {
+        &self.texts
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_primer_rate(&self) -> f64 {
+        self.primer_rate
+    }
+
+    pub fn get_case_noise(&self) -> f64 {
+        self.case_noise
+    }
+
+    pub fn get_names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn get_name_rate(&self) -> f64 {
+        self.name_rate
+    }
+
+    pub fn get_split(&self) -> [f64; 3] {
+        self.split
+    }
+}
+
+impl CorpusOutputData {
+    pub fn new(pairs: Vec<ParallelPair>) -> Self {
+        CorpusOutputData { pairs }
+    }
+
+    pub fn get_pairs(&self) -> &[ParallelPair] {
+        &self.pairs
+    }
+}
+
+pub struct CorpusInteractor {
+    translate_port: Box<dyn TranslateInputPort + Sync + Send>,
+}
+
+impl CorpusInteractor {
+    pub fn new() -> CorpusInteractor {
+        Self::with_translate_port(Box::new(TranslateInteractor::new()))
+    }
+
+    pub fn with_translate_port(
+        translate_port: Box<dyn TranslateInputPort + Sync + Send>,
+    ) -> CorpusInteractor {
+        CorpusInteractor { translate_port }
+    }
+
+    //名前の部分は変換せず、それ以外を覚えた文字を除いて大文字・小文字を残したまま変換する
+    fn encode_line(
+        &self,
+        text: &str,
+        name: Option<Range<usize>>,
+        primers: &[char],
+    ) -> Result<String, UsecaseError> {
+        let mut options = TranslationOptions::builder().with_case(CaseMode::Preserve);
+        if let Some(name) = name {
+            options = options.with_excluded(&[name]);
+        }
+        if !primers.is_empty() {
+            options = options.with_primers(primers);
+        }
+        let translate_input_data =
+            TranslateInputData::new(text, Direction::Encode, options.build());
+        let translated = self.translate_port.translate(translate_input_data)?;
+        Ok(translated.get_text().to_string())
+    }
+}
+
+impl Default for CorpusInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CorpusInputPort for CorpusInteractor {
+    fn generate(&self, input_data: CorpusInputData) -> Result<CorpusOutputData, UsecaseError> {
+        let split = input_data.get_split();
+        if split.iter().any(|ratio| *ratio < 0.0) || split.iter().sum::<f64>() <= 0.0 {
+            return Err(UsecaseError::InvalidSplit);
+        }
+        for (name, rate) in [
+            ("Primer rate", input_data.get_primer_rate()),
+            ("Case noise", input_data.get_case_noise()),
+            ("Name rate", input_data.get_name_rate()),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(UsecaseError::InvalidRate { option: name });
+            }
+        }
+
+        let mut random = SeededRandom::new(input_data.get_seed());
+        let mut pairs = Vec::new();
+        for text in input_data.get_texts() {
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            //名前は単語の区切りに差し込む
+            let mut words: Vec<&str> = text.split(' ').collect();
+            let mut names = Vec::new();
+            let mut name_range = None;
+            if random.chance(input_data.get_name_rate()) {
+                let candidates = input_data.get_names();
+                let name = &candidates[random.below(candidates.len())];
+                let k = random.below(words.len() + 1);
+                let start: usize = words[..k].iter().map(|word| word.chars().count() + 1).sum();
+                words.insert(k, name);
+                name_range = Some(start..start + name.chars().count());
+                names.push(name.clone());
+            }
+            let plaintext = words.join(" ");
+
+            let mut primers = Vec::new();
+            if random.chance(input_data.get_primer_rate()) {
+                let mut letters: Vec<char> = ('A'..='Z').collect();
+                random.shuffle(&mut letters);
+                primers = letters[..1 + random.below(letters.len() - 1)].to_vec();
+                primers.sort();
+            }
+
+            let ciphertext = self.encode_line(&plaintext, name_range, &primers)?;
+            let (plaintext, ciphertext) = add_case_noise(
+                &plaintext,
+                &ciphertext,
+                input_data.get_case_noise(),
+                &mut random,
+            );
+
+            pairs.push(ParallelPair {
+                plaintext,
+                ciphertext,
+                split: Split::Train,
+                primers,
+                names,
+            });
+        }
+
+        //シャッフルした順に、割合どおりの文数で train・dev・test に分ける
+        let mut order: Vec<usize> = (0..pairs.len()).collect();
+        random.shuffle(&mut order);
+        for (position, index) in order.into_iter().enumerate() {
+            pairs[index].split = split_at(&split, pairs.len(), position);
+        }
+        Ok(CorpusOutputData::new(pairs))
+    }
+}
+
+//total 文のうち、シャッフル後に position 番目の文が入る組
+fn split_at(split: &[f64; 3], total: usize, position: usize) -> Split {
+    let sum: f64 = split.iter().sum();
+    let train = (total as f64 * split[0] / sum).round() as usize;
+    let dev = ((total as f64 * split[1] / sum).round() as usize).min(total - train);
+    if position < train {
+        Split::Train
+    } else if position < train + dev {
+        Split::Dev
+    } else {
+        Split::Test
+    }
+}
+
+//平文と暗号文の同じ位置の文字を小文字にする。文字数が合わないときはそのまま
+fn add_case_noise(
+    plaintext: &str,
+    ciphertext: &str,
+    rate: f64,
+    random: &mut SeededRandom,
+) -> (String, String) {
+    if rate == 0.0 || plaintext.chars().count() != ciphertext.chars().count() {
+        return (plaintext.to_string(), ciphertext.to_string());
+    }
+    plaintext
+        .chars()
+        .zip(ciphertext.chars())
+        .map(|(p, c)| {
+            if p.is_ascii_alphabetic() && c.is_ascii_alphabetic() && random.chance(rate) {
+                (p.to_ascii_lowercase(), c.to_ascii_lowercase())
+            } else {
+                (p, c)
+            }
+        })
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_generate_pairs() {
+        let corpus_port = CorpusInteractor::new();
+        let corpus_input_data = CorpusInputData::new(texts(&["hello", "", "じかんがねえ"]), 1);
+        let result = corpus_port.generate(corpus_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        let pairs: Vec<(&str, &str)> = output
+            .get_pairs()
+            .iter()
+            .map(|pair| (pair.plaintext(), pair.ciphertext()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("hello", "rammu"), ("じかんがねえ", "ギアンダメネ")]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_generate_keeps_spacing_and_case() {
+        let corpus_port = CorpusInteractor::new();
+        let lines = texts(&["hello  world", " Hello World ", "WHAT  IS  THAT?"]);
+        let output = corpus_port
+            .generate(CorpusInputData::new(lines, 1))
+            .unwrap();
+        let pairs: Vec<(&str, &str)> = output
+            .get_pairs()
+            .iter()
+            .map(|pair| (pair.plaintext(), pair.ciphertext()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("hello  world", "rammu  funmt"),
+                ("Hello World", "Rammu Funmt"),
+                ("WHAT  IS  THAT?", "FRYD  EC  DRYD?"),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_generate_names_between_spaces() {
+        let corpus_port = CorpusInteractor::new();
+        let names = ["Yuna".to_string()];
+        let input = CorpusInputData::new(texts(&["hi  there"]), 7).with_names(&names, 1.0);
+        let output = corpus_port.generate(input).unwrap();
+        let pair = &output.get_pairs()[0];
+        //名前は連続した空白の間にも入り、変換されない
+        assert_eq!(pair.plaintext(), "hi  Yuna there");
+        assert_eq!(pair.ciphertext(), "re  Yuna drana");
+        assert_eq!(pair.names(), ["Yuna"]);
+    }
+
+    #[test]
+    fn test_generate_exact_split_sizes() {
+        let corpus_port = CorpusInteractor::new();
+        let lines: Vec<String> = (0..97).map(|k| format!("LINE {}", k)).collect();
+        for seed in [1, 2, 3] {
+            let input = CorpusInputData::new(lines.clone(), seed).with_split(0.8, 0.1, 0.1);
+            let output = corpus_port.generate(input).unwrap();
+            let count = |split: Split| {
+                output
+                    .get_pairs()
+                    .iter()
+                    .filter(|pair| pair.split() == split)
+                    .count()
+            };
+            assert_eq!(
+                (count(Split::Train), count(Split::Dev), count(Split::Test)),
+                (78, 10, 9)
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_with_augmentations() {
+        let corpus_port = CorpusInteractor::new();
+        let lines: Vec<String> = (0..50).map(|k| format!("LINE NUMBER {}", k)).collect();
+        let input = || {
+            CorpusInputData::new(lines.clone(), 42)
+                .with_primer_rate(1.0)
+                .with_case_noise(0.5)
+                .with_names(&["YUNA".to_string()], 1.0)
+        };
+        let output = corpus_port.generate(input()).unwrap();
+        let again = corpus_port.generate(input()).unwrap();
+
+        for (pair, same) in output.get_pairs().iter().zip(again.get_pairs()) {
+            assert_eq!(pair.ciphertext(), same.ciphertext());
+            assert_eq!(pair.split(), same.split());
+            assert_eq!(pair.names(), ["YUNA"]);
+            assert!(!pair.primers().is_empty());
+            assert!(pair.ciphertext().to_uppercase().contains("YUNA"));
+        }
+        assert!(output
+            .get_pairs()
+            .iter()
+            .any(|pair| pair.split() == Split::Train));
+        assert!(output
+            .get_pairs()
+            .iter()
+            .any(|pair| pair.plaintext().chars().any(|c| c.is_ascii_lowercase())));
+    }
+
+    #[test]
+    fn test_generate_invalid_options() {
+        let corpus_port = CorpusInteractor::new();
+        let input = CorpusInputData::new(texts(&["A"]), 1).with_split(0.0, 0.0, 0.0);
+        assert!(corpus_port.generate(input).is_err());
+
+        let input = CorpusInputData::new(texts(&["A"]), 1).with_case_noise(1.5);
+        assert!(corpus_port.generate(input).is_err());
+    }
+}
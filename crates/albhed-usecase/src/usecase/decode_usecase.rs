@@ -0,0 +1,302 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Language, Script};
+use crate::domain::lint::LintFinding;
+use crate::domain::markup::TagHandling;
+use crate::domain::pipeline::{Pipeline, StageReport};
+use crate::domain::protection::{EntityKind, ProtectedSpan};
+use crate::domain::table_registry::TableRegistry;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::input_lint::LintMode;
+use crate::usecase::translate_usecase::{
+    Direction, TranslateInputData, TranslateInputPort, TranslateInteractor, TranslationOptions,
+};
+
+pub trait DecodeInputPort {
+    fn decode(&self, input_data: DecodeInputData) -> Result<DecodeOutputData, UsecaseError>;
+}
+
+pub struct DecodeInputData {
+    text: String,
+    table: Option<String>,
+    languages: Option<Vec<Language>>,
+    protections: Option<Vec<EntityKind>>,
+    markup: Option<TagHandling>,
+    lint_mode: Option<LintMode>,
+    pipeline: Option<Pipeline>,
+}
+
+pub struct DecodeOutputData {
+    text: String,
+    protected: Option<Vec<ProtectedSpan>>,
+    lint_findings: Vec<LintFinding>,
+    stage_reports: Vec<StageReport>,
+}
+
+impl DecodeInputData {
+    pub fn new(input: &str) -> Self {
+        DecodeInputData {
+            text: input.to_string(),
+            table: None,
+            languages: None,
+            protections: None,
+            markup: None,
+            lint_mode: None,
+            pipeline: None,
+        }
+    }
+
+    pub fn with_table(mut self, table: &str) -> Self {
+        self.table = Some(table.to_string());
+        self
+    }
+
+    pub fn with_languages(mut self, languages: &[Language]) -> Self {
+        self.languages = Some(languages.to_vec());
+        self
+    }
+
+    pub fn with_protections(mut self, protections: &[EntityKind]) -> Self {
+        self.protections = Some(protections.to_vec());
+        self
+    }
+
+    //タグで囲まれた部分だけを変換する
+    pub fn with_markup(mut self, handling: TagHandling) -> Self {
+        self.markup = Some(handling);
+        self
+    }
+
+    pub fn with_lint_mode(mut self, mode: LintMode) -> Self {
+        self.lint_mode = Some(mode);
+        self
+    }
+
+    //復号の前後に入れる処理。指定がなければサーバーの設定を使う
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    pub fn get_languages(&self) -> Option<&[Language]> {
+        self.languages.as_deref()
+    }
+
+    pub fn get_protections(&self) -> Option<&[EntityKind]> {
+        self.protections.as_deref()
+    }
+
+    pub fn get_markup(&self) -> Option<TagHandling> {
+        self.markup
+    }
+
+    pub fn get_lint_mode(&self) -> Option<LintMode> {
+        self.lint_mode
+    }
+
+    pub fn get_pipeline(&self) -> Option<&Pipeline> {
+        self.pipeline.as_ref()
+    }
+}
+
+impl DecodeOutputData {
+    pub fn new(output: &str) -> Self {
+        DecodeOutputData {
+            text: output.to_string(),
+            protected: None,
+            lint_findings: Vec::new(),
+            stage_reports: Vec::new(),
+        }
+    }
+
+    pub fn with_protected(mut self, protected: Vec<ProtectedSpan>) -> Self {
+        self.protected = Some(protected);
+        self
+    }
+
+    pub fn with_lint_findings(mut self, findings: Vec<LintFinding>) -> Self {
+        self.lint_findings = findings;
+        self
+    }
+
+    pub fn with_stage_reports(mut self, reports: Vec<StageReport>) -> Self {
+        self.stage_reports = reports;
+        self
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    //保護指定があったときだけSomeになる
+    pub fn get_protected(&self) -> Option<&[ProtectedSpan]> {
+        self.protected.as_deref()
+    }
+
+    pub fn get_lint_findings(&self) -> &[LintFinding] {
+        &self.lint_findings
+    }
+
+    pub fn get_stage_reports(&self) -> &[StageReport] {
+        &self.stage_reports
+    }
+}
+
+//TranslateInteractor の復号だけを使う薄いラッパー
+pub struct DecodeInteractor {
+    translator: TranslateInteractor,
+}
+
+impl DecodeInteractor {
+    pub fn new() -> DecodeInteractor {
+        Self::with_registry(Arc::new(TableRegistry::new()))
+    }
+
+    pub fn with_registry(registry: Arc<TableRegistry>) -> DecodeInteractor {
+        DecodeInteractor {
+            translator: TranslateInteractor::with_registry(registry),
+        }
+    }
+
+    //リクエストで指定がないときの入力検査
+    pub fn with_lint_mode(mut self, mode: LintMode) -> Self {
+        self.translator = self.translator.with_lint_mode(mode);
+        self
+    }
+
+    //リクエストで指定がないときの前後処理
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.translator = self.translator.with_pipeline(pipeline);
+        self
+    }
+
+    pub fn with_parallel_threshold(mut self, bytes: usize) -> Self {
+        self.translator = self.translator.with_parallel_threshold(bytes);
+        self
+    }
+}
+
+impl Default for DecodeInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecodeInputPort for DecodeInteractor {
+    fn decode(&self, input_data: DecodeInputData) -> Result<DecodeOutputData, UsecaseError> {
+        let mut options = TranslationOptions::builder();
+        if let Some(table) = input_data.get_table() {
+            options = options.with_table(table);
+        }
+        if let Some(languages) = input_data.get_languages() {
+            let scripts: Vec<Script> = languages.iter().map(Language::script).collect();
+            options = options.with_scripts(&scripts);
+        }
+        if let Some(protections) = input_data.get_protections() {
+            options = options.with_protections(protections);
+        }
+        if let Some(markup) = input_data.get_markup() {
+            options = options.with_markup(markup);
+        }
+        if let Some(mode) = input_data.get_lint_mode() {
+            options = options.with_lint_mode(mode);
+        }
+        if let Some(pipeline) = input_data.get_pipeline() {
+            options = options.with_pipeline(pipeline.clone());
+        }
+
+        let translated = self.translator.translate(TranslateInputData::new(
+            input_data.get_text(),
+            Direction::Decode,
+            options.build(),
+        ))?;
+        let output_data = DecodeOutputData::new(translated.get_text())
+            .with_lint_findings(translated.get_lint_findings())
+            .with_stage_reports(translated.get_metadata().get_stage_reports().to_vec());
+        Ok(match translated.get_protected() {
+            Some(spans) => output_data.with_protected(spans.to_vec()),
+            None => output_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "japanese"))]
+    fn test_decode_without_japanese() {
+        let decode_port = DecodeInteractor::new();
+        let result = decode_port.decode(DecodeInputData::new("マギレヤキセ！"));
+
+        assert_eq!(result.unwrap().get_text(), "マギレヤキセ！");
+    }
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_decode() {
+        let decode_port = DecodeInteractor::new();
+        let decode_input_data = DecodeInputData::new("マギレヤキセ！");
+        let result = decode_port.decode(decode_input_data);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get_text(), "はじめまして！");
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_decode_with_languages() {
+        let decode_port = DecodeInteractor::new();
+        let decode_input_data =
+            DecodeInputData::new("RAMMU ヨンシヒマ").with_languages(&[Language::English]);
+        let result = decode_port.decode(decode_input_data);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get_text(), "HELLO ヨンシヒマ");
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_decode_with_markup() {
+        let decode_port = DecodeInteractor::new();
+        let decode_input_data =
+            DecodeInputData::new("RAMMU [ab]RAMMU[/ab]").with_markup(TagHandling::Strip);
+        let result = decode_port.decode(decode_input_data);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get_text(), "RAMMU HELLO");
+    }
+
+    #[test]
+    fn test_decode_with_unbalanced_markup() {
+        let decode_port = DecodeInteractor::new();
+        let decode_input_data = DecodeInputData::new("[ab]RAMMU").with_markup(TagHandling::Keep);
+        let result = decode_port.decode(decode_input_data);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_decode_with_pipeline() {
+        let decode_port = DecodeInteractor::new();
+        let pipeline = Pipeline::profile("normalize").unwrap();
+        let decode_input_data = DecodeInputData::new("  ＲＡＭＭＵ   ").with_pipeline(pipeline);
+        let result = decode_port.decode(decode_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_text(), "HELLO");
+        assert_eq!(output.get_stage_reports().len(), 4);
+        assert_eq!(output.get_stage_reports()[2].changes()[0].after(), "RAMMU");
+    }
+}
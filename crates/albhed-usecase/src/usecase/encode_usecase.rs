@@ -0,0 +1,533 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Language, Script};
+use crate::domain::lint::LintFinding;
+use crate::domain::markup::TagHandling;
+use crate::domain::pipeline::{Pipeline, StageReport};
+use crate::domain::pronunciation::WordPronunciation;
+use crate::domain::protection::{EntityKind, ProtectedSpan};
+use crate::domain::ssml::SsmlOptions;
+use crate::domain::table_registry::TableRegistry;
+use crate::usecase::error::UsecaseError;
+use crate::usecase::input_lint::LintMode;
+use crate::usecase::translate_usecase::{
+    Direction, TranslateInputData, TranslateInputPort, TranslateInteractor, TranslationOptions,
+};
+
+pub trait EncodeInputPort {
+    fn encode(&self, input_data: EncodeInputData) -> Result<EncodeOutputData, UsecaseError>;
+}
+
+pub struct EncodeInputData {
+    text: String,
+    table: Option<String>,
+    languages: Option<Vec<Language>>,
+    protections: Option<Vec<EntityKind>>,
+    markup: Option<TagHandling>,
+    lint_mode: Option<LintMode>,
+    pronunciation: bool,
+    ssml: Option<SsmlOptions>,
+    primers: Option<Vec<char>>,
+    pipeline: Option<Pipeline>,
+}
+
+pub struct EncodeOutputData {
+    text: String,
+    protected: Option<Vec<ProtectedSpan>>,
+    lint_findings: Vec<LintFinding>,
+    pronunciation: Option<Vec<WordPronunciation>>,
+    ssml: Option<String>,
+    stage_reports: Vec<StageReport>,
+}
+
+impl EncodeInputData {
+    pub fn new(input: &str) -> Self {
+        EncodeInputData {
+            text: input.to_string(),
+            table: None,
+            languages: None,
+            protections: None,
+            markup: None,
+            lint_mode: None,
+            pronunciation: false,
+            ssml: None,
+            primers: None,
+            pipeline: None,
+        }
+    }
+
+    pub fn with_table(mut self, table: &str) -> Self {
+        self.table = Some(table.to_string());
+        self
+    }
+
+    pub fn with_languages(mut self, languages: &[Language]) -> Self {
+        self.languages = Some(languages.to_vec());
+        self
+    }
+
+    pub fn with_protections(mut self, protections: &[EntityKind]) -> Self {
+        self.protections = Some(protections.to_vec());
+        self
+    }
+
+    //タグで囲まれた部分だけを変換する
+    pub fn with_markup(mut self, handling: TagHandling) -> Self {
+        self.markup = Some(handling);
+        self
+    }
+
+    pub fn with_lint_mode(mut self, mode: LintMode) -> Self {
+        self.lint_mode = Some(mode);
+        self
+    }
+
+    //変換結果の読み方も返す
+    pub fn with_pronunciation(mut self) -> Self {
+        self.pronunciation = true;
+        self
+    }
+
+    //アルベド語辞書で覚えた文字は変換せずに残す
+    pub fn with_primers(mut self, letters: &[char]) -> Self {
+        self.primers = Some(letters.to_vec());
+        self
+    }
+
+    //変換結果を読み上げ用のSSMLでも返す
+    pub fn with_ssml(mut self, options: SsmlOptions) -> Self {
+        self.ssml = Some(options);
+        self
+    }
+
+    //暗号化の前後に入れる処理。指定がなければサーバーの設定を使う
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    pub fn get_languages(&self) -> Option<&[Language]> {
+        self.languages.as_deref()
+    }
+
+    pub fn get_protections(&self) -> Option<&[EntityKind]> {
+        self.protections.as_deref()
+    }
+
+    pub fn get_markup(&self) -> Option<TagHandling> {
+        self.markup
+    }
+
+    pub fn get_lint_mode(&self) -> Option<LintMode> {
+        self.lint_mode
+    }
+
+    pub fn get_pronunciation(&self) -> bool {
+        self.pronunciation
+    }
+
+    pub fn get_ssml(&self) -> Option<&SsmlOptions> {
+        self.ssml.as_ref()
+    }
+
+    pub fn get_primers(&self) -> Option<&[char]> {
+        self.primers.as_deref()
+    }
+
+    pub fn get_pipeline(&self) -> Option<&Pipeline> {
+        self.pipeline.as_ref()
+    }
+}
+
+impl EncodeOutputData {
+    pub fn new(output: &str) -> Self {
+        EncodeOutputData {
+            text: output.to_string(),
+            protected: None,
+            lint_findings: Vec::new(),
+            pronunciation: None,
+            ssml: None,
+            stage_reports: Vec::new(),
+        }
+    }
+
+    pub fn with_protected(mut self, protected: Vec<ProtectedSpan>) -> Self {
+        self.protected = Some(protected);
+        self
+    }
+
+    pub fn with_lint_findings(mut self, findings: Vec<LintFinding>) -> Self {
+        self.lint_findings = findings;
+        self
+    }
+
+    pub fn with_pronunciation(mut self, pronunciation: Vec<WordPronunciation>) -> Self {
+        self.pronunciation = Some(pronunciation);
+        self
+    }
+
+    pub fn with_ssml(mut self, ssml: String) -> Self {
+        self.ssml = Some(ssml);
+        self
+    }
+
+    pub fn with_stage_reports(mut self, reports: Vec<StageReport>) -> Self {
+        self.stage_reports = reports;
+        self
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    //保護指定があったときだけSomeになる
+    pub fn get_protected(&self) -> Option<&[ProtectedSpan]> {
+        self.protected.as_deref()
+    }
+
+    pub fn get_lint_findings(&self) -> &[LintFinding] {
+        &self.lint_findings
+    }
+
+    //読み方の指定があったときだけSomeになる
+    pub fn get_pronunciation(&self) -> Option<&[WordPronunciation]> {
+        self.pronunciation.as_deref()
+    }
+
+    pub fn get_ssml(&self) -> Option<&str> {
+        self.ssml.as_deref()
+    }
+
+    pub fn get_stage_reports(&self) -> &[StageReport] {
+        &self.stage_reports
+    }
+}
+
+//TranslateInteractor の暗号化だけを使う薄いラッパー
+pub struct EncodeInteractor {
+    translator: TranslateInteractor,
+}
+
+impl EncodeInteractor {
+    pub fn new() -> EncodeInteractor {
+        Self::with_registry(Arc::new(TableRegistry::new()))
+    }
+
+    pub fn with_registry(registry: Arc<TableRegistry>) -> EncodeInteractor {
+        EncodeInteractor {
+            translator: TranslateInteractor::with_registry(registry),
+        }
+    }
+
+    //リクエストで指定がないときの入力検査
+    pub fn with_lint_mode(mut self, mode: LintMode) -> Self {
+        self.translator = self.translator.with_lint_mode(mode);
+        self
+    }
+
+    //リクエストで指定がないときの前後処理
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.translator = self.translator.with_pipeline(pipeline);
+        self
+    }
+
+    pub fn with_parallel_threshold(mut self, bytes: usize) -> Self {
+        self.translator = self.translator.with_parallel_threshold(bytes);
+        self
+    }
+}
+
+impl Default for EncodeInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EncodeInputPort for EncodeInteractor {
+    fn encode(&self, input_data: EncodeInputData) -> Result<EncodeOutputData, UsecaseError> {
+        let mut options = TranslationOptions::builder();
+        if let Some(table) = input_data.get_table() {
+            options = options.with_table(table);
+        }
+        if let Some(languages) = input_data.get_languages() {
+            let scripts: Vec<Script> = languages.iter().map(Language::script).collect();
+            options = options.with_scripts(&scripts);
+        }
+        if let Some(protections) = input_data.get_protections() {
+            options = options.with_protections(protections);
+        }
+        if let Some(markup) = input_data.get_markup() {
+            options = options.with_markup(markup);
+        }
+        if let Some(mode) = input_data.get_lint_mode() {
+            options = options.with_lint_mode(mode);
+        }
+        if let Some(pipeline) = input_data.get_pipeline() {
+            options = options.with_pipeline(pipeline.clone());
+        }
+        if let Some(letters) = input_data.get_primers() {
+            options = options.with_primers(letters);
+        }
+        if input_data.get_pronunciation() {
+            options = options.with_pronunciation();
+        }
+        if let Some(ssml) = input_data.get_ssml() {
+            options = options.with_ssml(ssml.clone());
+        }
+
+        let translated = self.translator.translate(TranslateInputData::new(
+            input_data.get_text(),
+            Direction::Encode,
+            options.build(),
+        ))?;
+        let mut output_data = EncodeOutputData::new(translated.get_text())
+            .with_lint_findings(translated.get_lint_findings())
+            .with_stage_reports(translated.get_metadata().get_stage_reports().to_vec());
+        if let Some(pronunciation) = translated.get_pronunciation() {
+            output_data = output_data.with_pronunciation(pronunciation.to_vec());
+        }
+        if let Some(ssml) = translated.get_ssml() {
+            output_data = output_data.with_ssml(ssml.to_string());
+        }
+        Ok(match translated.get_protected() {
+            Some(spans) => output_data.with_protected(spans.to_vec()),
+            None => output_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::cipher_plugin::test_plugins::Reverse;
+    use crate::domain::keyed_table::KeyedTable;
+
+    //組み込まなかった変換ルールの文字はそのまま残る
+    #[test]
+    #[cfg(not(feature = "english"))]
+    fn test_encode_without_english() {
+        let encode_port = EncodeInteractor::new();
+        let result = encode_port.encode(EncodeInputData::new("HELLO WORLD"));
+
+        assert_eq!(result.unwrap().get_text(), "HELLO WORLD");
+    }
+
+    #[test]
+    #[cfg(not(feature = "japanese"))]
+    fn test_encode_without_japanese() {
+        let encode_port = EncodeInteractor::new();
+        let result = encode_port.encode(EncodeInputData::new("じかんがねえ！"));
+
+        assert_eq!(result.unwrap().get_text(), "じかんがねえ！");
+    }
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_encode() {
+        let encode_port = EncodeInteractor::new();
+        let encode_input_data = EncodeInputData::new("じかんがねえ！ さっさと はっしんしろ！");
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap().get_text(),
+            "ギアンダメネ！ ラッラソ マッキンキノ！"
+        );
+    }
+
+    #[test]
+    fn test_encode_with_table() {
+        let registry = Arc::new(TableRegistry::new());
+        let keyed = KeyedTable::generate("zebras", &[Script::Latin], true, b"").unwrap();
+        let hash = keyed.hash().to_string();
+        registry.register_keyed(&hash, keyed.into_table()).unwrap();
+
+        let encode_port = EncodeInteractor::with_registry(registry);
+        let encode_input_data = EncodeInputData::new("ABCDEZ").with_table(&hash);
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get_text(), "ZEBRAY");
+    }
+
+    #[test]
+    fn test_encode_with_unknown_table() {
+        let encode_port = EncodeInteractor::new();
+        let encode_input_data = EncodeInputData::new("ABC").with_table("unknown");
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_protections() {
+        let encode_port = EncodeInteractor::new();
+        let encode_input_data = EncodeInputData::new("HI @RIKKU, SEE https://example.com")
+            .with_protections(&[EntityKind::Mention, EntityKind::Url]);
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_text(), "RE @RIKKU, CAA https://example.com");
+        assert_eq!(output.get_protected().unwrap().len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_lint_mode() {
+        let encode_port = EncodeInteractor::new().with_lint_mode(LintMode::Reject);
+        let result = encode_port.encode(EncodeInputData::new("\u{0410}LBHED"));
+        assert!(result.is_err());
+
+        let encode_input_data =
+            EncodeInputData::new("\u{0410}LBHED").with_lint_mode(LintMode::Normalize);
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_text(), "YMPRAT");
+        assert_eq!(output.get_lint_findings().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_pronunciation() {
+        let encode_port = EncodeInteractor::new();
+        let encode_input_data = EncodeInputData::new("ALBHED").with_pronunciation();
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        let pronunciation = output.get_pronunciation().unwrap();
+        assert_eq!(pronunciation[0].respelling(), "IHM-praht");
+        assert!(EncodeInteractor::new()
+            .encode(EncodeInputData::new("ALBHED"))
+            .unwrap()
+            .get_pronunciation()
+            .is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_ssml() {
+        let encode_port = EncodeInteractor::new();
+        let encode_input_data = EncodeInputData::new("HI @RIKKU")
+            .with_protections(&[EntityKind::Mention])
+            .with_ssml(SsmlOptions::new("en-US"));
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        let ssml = result.unwrap().get_ssml().unwrap().to_string();
+        assert!(ssml.contains(
+            "<phoneme alphabet=\"ipa\" ph=\"ɹe\">RE</phoneme> <lang xml:lang=\"en-US\">@RIKKU</lang>"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_primers() {
+        let encode_port = EncodeInteractor::new();
+        let encode_input_data = EncodeInputData::new("HELLO").with_primers(&['E', 'L']);
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get_text(), "RELLU");
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_pipeline() {
+        use crate::domain::pipeline::Phase;
+
+        let encode_port = EncodeInteractor::new();
+        let pipeline = Pipeline::from_names(&["trim", "uppercase"], &["lowercase"]).unwrap();
+        let encode_input_data = EncodeInputData::new(" hello ").with_pipeline(pipeline);
+        let result = encode_port.encode(encode_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_text(), "rammu");
+        let stages: Vec<(&str, Phase)> = output
+            .get_stage_reports()
+            .iter()
+            .map(|report| (report.stage(), report.phase()))
+            .collect();
+        assert_eq!(
+            stages,
+            vec![
+                ("trim", Phase::Pre),
+                ("uppercase", Phase::Pre),
+                ("lowercase", Phase::Post),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_pipeline_and_protections() {
+        use crate::domain::pipeline::Phase;
+
+        //前後の処理は保護した部分を変えない
+        let pipeline = Pipeline::profile("game")
+            .unwrap()
+            .with_post(crate::domain::pipeline::processor("lowercase").unwrap());
+        let encode_input_data =
+            EncodeInputData::new("  ｈｅｌｌｏ https://example.com/Path `Code` {Name}  ")
+                .with_protections(&[EntityKind::Url, EntityKind::Code, EntityKind::Placeholder])
+                .with_pipeline(pipeline);
+        let output = EncodeInteractor::new().encode(encode_input_data).unwrap();
+        assert_eq!(
+            output.get_text(),
+            "rammu https://example.com/Path `Code` {Name}"
+        );
+
+        //報告には目印ではなく元の文字列が出る
+        let reports = output.get_stage_reports();
+        let uppercase = reports.iter().find(|r| r.stage() == "uppercase").unwrap();
+        assert_eq!(uppercase.changes()[0].before(), "hello");
+        let lowercase = reports.iter().find(|r| r.phase() == Phase::Post).unwrap();
+        assert!(lowercase
+            .changes()
+            .iter()
+            .all(|change| !change.before().contains("https")));
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_encode_with_default_pipeline() {
+        let encode_port = EncodeInteractor::new().with_pipeline(Pipeline::profile("game").unwrap());
+        let result = encode_port.encode(EncodeInputData::new("ｈｅｌｌｏ"));
+        assert_eq!(result.unwrap().get_text(), "RAMMU");
+
+        let encode_input_data = EncodeInputData::new("ｈｅｌｌｏ").with_pipeline(Pipeline::new());
+        let result = encode_port.encode(encode_input_data);
+        assert_eq!(result.unwrap().get_text(), "ｈｅｌｌｏ");
+    }
+
+    #[test]
+    fn test_encode_with_plugin() {
+        let registry = Arc::new(TableRegistry::new());
+        registry
+            .register_plugin("reverse", "", Arc::new(Reverse))
+            .unwrap();
+        let encode_port = EncodeInteractor::with_registry(registry);
+        let result = encode_port.encode(EncodeInputData::new("AB CD").with_table("reverse"));
+        assert_eq!(result.unwrap().get_text(), "DC BA");
+
+        //保護した部分で区切られ、前後はそれぞれ別々にプラグインに渡される
+        let encode_input_data = EncodeInputData::new("AB @RIKKU CD")
+            .with_table("reverse")
+            .with_protections(&[EntityKind::Mention]);
+        let result = encode_port.encode(encode_input_data);
+        assert_eq!(result.unwrap().get_text(), " BA@RIKKUDC ");
+    }
+}
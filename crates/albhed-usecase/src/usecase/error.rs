@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+use crate::domain::error::{DomainError, EntityError};
+
+#[derive(Debug, Error)]
+pub enum UsecaseError {
+    #[error("Unknown table: {0}")]
+    UnknownTable(String),
+    #[error("Cipher plugin {0} has no alphabet")]
+    NoAlphabet(String),
+    #[error("Unknown lint mode: {0}")]
+    UnknownLintMode(String),
+    #[error("Suspicious character U+{:04X} at {position}", *character as u32)]
+    SuspiciousCharacter { character: char, position: usize },
+    #[error("Name count must be between 1 and {max}")]
+    InvalidNameCount { max: usize },
+    #[error("Split ratios must be non-negative and not all zero")]
+    InvalidSplit,
+    #[error("{option} must be between 0 and 1")]
+    InvalidRate { option: &'static str },
+    #[error(transparent)]
+    Domain(#[from] DomainError),
+}
+
+impl UsecaseError {
+    //APIの利用者に返す、変わらない識別子
+    pub fn code(&self) -> &'static str {
+        match self {
+            UsecaseError::UnknownTable(_) => "unknown_table",
+            UsecaseError::NoAlphabet(_) => "no_alphabet",
+            UsecaseError::UnknownLintMode(_) => "unknown_lint_mode",
+            UsecaseError::SuspiciousCharacter { .. } => "suspicious_character",
+            UsecaseError::InvalidNameCount { .. } => "invalid_name_count",
+            UsecaseError::InvalidSplit => "invalid_split",
+            UsecaseError::InvalidRate { .. } => "invalid_rate",
+            UsecaseError::Domain(error) => error.code(),
+        }
+    }
+}
+
+impl From<EntityError> for UsecaseError {
+    fn from(error: EntityError) -> Self {
+        UsecaseError::Domain(error.into())
+    }
+}
@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use crate::domain::lint::{find_suspicious, normalize_homoglyphs, LintFinding};
+use crate::usecase::error::UsecaseError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintMode {
+    Off,
+    Report,
+    Normalize,
+    Reject,
+}
+
+impl FromStr for LintMode {
+    type Err = UsecaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(LintMode::Off),
+            "report" => Ok(LintMode::Report),
+            "normalize" => Ok(LintMode::Normalize),
+            "reject" => Ok(LintMode::Reject),
+            _ => Err(UsecaseError::UnknownLintMode(s.to_string())),
+        }
+    }
+}
+
+//入力を検査し、(変換に使うテキスト, 検出結果) を返す
+pub fn lint_input(text: &str, mode: LintMode) -> Result<(String, Vec<LintFinding>), UsecaseError> {
+    if mode == LintMode::Off {
+        return Ok((text.to_string(), Vec::new()));
+    }
+
+    let findings = find_suspicious(text);
+    match (mode, findings.first()) {
+        (LintMode::Reject, Some(finding)) => Err(UsecaseError::SuspiciousCharacter {
+            character: finding.character(),
+            position: finding.position(),
+        }),
+        (LintMode::Normalize, _) => Ok((normalize_homoglyphs(text), findings)),
+        _ => Ok((text.to_string(), findings)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_input_modes() {
+        let text = "\u{0410}LBHED";
+        assert_eq!(
+            lint_input(text, LintMode::Off).unwrap(),
+            (text.to_string(), vec![])
+        );
+
+        let (reported, findings) = lint_input(text, LintMode::Report).unwrap();
+        assert_eq!((reported.as_str(), findings.len()), (text, 1));
+
+        let (normalized, _) = lint_input(text, LintMode::Normalize).unwrap();
+        assert_eq!(normalized, "ALBHED");
+
+        assert_eq!(
+            lint_input(text, LintMode::Reject)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Suspicious character U+0410 at 0"
+        );
+        assert!(lint_input("ALBHED", LintMode::Reject).is_ok());
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::domain::name_generator::{GeneratedName, NameGenerator, NameOptions};
+use crate::usecase::error::UsecaseError;
+
+const MAX_NAMES: usize = 100;
+
+pub trait NameInputPort {
+    fn generate(&self, input_data: NameInputData) -> Result<NameOutputData, UsecaseError>;
+}
+
+pub struct NameInputData {
+    count: usize,
+    syllables: Option<usize>,
+    neutral: bool,
+    seed: Option<u64>,
+}
+
+pub struct NameOutputData {
+    names: Vec<GeneratedName>,
+    seed: u64,
+}
+
+impl NameInputData {
+    pub fn new(count: usize) -> Self {
+        NameInputData {
+            count,
+            syllables: None,
+            neutral: false,
+            seed: None,
+        }
+    }
+
+    pub fn with_syllables(mut self, syllables: usize) -> Self {
+        self.syllables = Some(syllables);
+        self
+    }
+
+    pub fn with_neutral_style(mut self) -> Self {
+        self.neutral = true;
+        self
+    }
+
+    //同じシードなら同じ名前を返す
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn get_syllables(&self) -> Option<usize> {
+        self.syllables
+    }
+
+    pub fn get_neutral(&self) -> bool {
+        self.neutral
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl NameOutputData {
+    pub fn new(names: Vec<GeneratedName>, seed: u64) -> Self {
+        NameOutputData { names, seed }
+    }
+
+    pub fn get_names(&self) -> &[GeneratedName] {
+        &self.names
+    }
+
+    //シードを指定しなかったときに使ったシード
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+pub struct NameInteractor {
+    generator: NameGenerator,
+}
+
+impl NameInteractor {
+    pub fn new() -> NameInteractor {
+        NameInteractor {
+            generator: NameGenerator::new(),
+        }
+    }
+}
+
+impl Default for NameInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NameInputPort for NameInteractor {
+    fn generate(&self, input_data: NameInputData) -> Result<NameOutputData, UsecaseError> {
+        if !(1..=MAX_NAMES).contains(&input_data.get_count()) {
+            return Err(UsecaseError::InvalidNameCount { max: MAX_NAMES });
+        }
+
+        let seed = input_data.get_seed().unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+        });
+        let mut options = NameOptions::new(seed);
+        if let Some(syllables) = input_data.get_syllables() {
+            options = options.with_syllables(syllables);
+        }
+        if input_data.get_neutral() {
+            options = options.with_neutral_style();
+        }
+
+        let names = self.generator.generate(&options, input_data.get_count())?;
+        Ok(NameOutputData::new(names, seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_names() {
+        let name_port = NameInteractor::new();
+        let result = name_port.generate(NameInputData::new(3).with_seed(42));
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_names().len(), 3);
+        assert_eq!(output.get_seed(), 42);
+
+        let again = name_port
+            .generate(NameInputData::new(3).with_seed(output.get_seed()))
+            .unwrap();
+        let names = |output: &NameOutputData| -> Vec<String> {
+            output
+                .get_names()
+                .iter()
+                .map(|name| name.name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&output), names(&again));
+    }
+
+    #[test]
+    fn test_generate_too_many_names() {
+        let name_port = NameInteractor::new();
+        let result = name_port.generate(NameInputData::new(101));
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::domain::entities::AlBhedText;
+use crate::domain::spellcheck::{Misspelling, SpellChecker};
+use crate::usecase::error::UsecaseError;
+
+pub trait SpellcheckInputPort {
+    fn spellcheck(
+        &self,
+        input_data: SpellcheckInputData,
+    ) -> Result<SpellcheckOutputData, UsecaseError>;
+}
+
+pub struct SpellcheckInputData {
+    text: String,
+}
+
+pub struct SpellcheckOutputData {
+    misspellings: Vec<Misspelling>,
+}
+
+impl SpellcheckInputData {
+    pub fn new(input: &str) -> Self {
+        SpellcheckInputData {
+            text: input.to_string(),
+        }
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl SpellcheckOutputData {
+    pub fn new(misspellings: Vec<Misspelling>) -> Self {
+        SpellcheckOutputData { misspellings }
+    }
+
+    pub fn get_misspellings(&self) -> &[Misspelling] {
+        &self.misspellings
+    }
+}
+
+pub struct SpellcheckInteractor {
+    checker: SpellChecker,
+}
+
+impl SpellcheckInteractor {
+    pub fn new() -> SpellcheckInteractor {
+        SpellcheckInteractor {
+            checker: SpellChecker::new(),
+        }
+    }
+}
+
+impl Default for SpellcheckInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpellcheckInputPort for SpellcheckInteractor {
+    fn spellcheck(
+        &self,
+        input_data: SpellcheckInputData,
+    ) -> Result<SpellcheckOutputData, UsecaseError> {
+        let albhed_text = AlBhedText::new(input_data.get_text())?;
+        Ok(SpellcheckOutputData::new(self.checker.check(&albhed_text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_spellcheck() {
+        let spellcheck_port = SpellcheckInteractor::new();
+        let spellcheck_input_data = SpellcheckInputData::new("FRYD EC DRYT?");
+        let result = spellcheck_port.spellcheck(spellcheck_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_misspellings().len(), 1);
+        assert_eq!(output.get_misspellings()[0].suggestions()[0].text(), "DRYD");
+    }
+
+    #[test]
+    fn test_spellcheck_empty() {
+        let spellcheck_port = SpellcheckInteractor::new();
+        let result = spellcheck_port.spellcheck(SpellcheckInputData::new(""));
+        assert!(result.is_err());
+    }
+}
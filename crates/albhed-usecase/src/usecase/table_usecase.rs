@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use crate::domain::entities::Script;
+use crate::domain::keyed_table::KeyedTable;
+use crate::domain::table_registry::TableRegistry;
+use crate::usecase::error::UsecaseError;
+
+pub trait TableInputPort {
+    fn generate(&self, input_data: TableInputData) -> Result<TableOutputData, UsecaseError>;
+}
+
+pub struct TableInputData {
+    passphrase: String,
+    scripts: Vec<Script>,
+    allow_self_mapping: bool,
+}
+
+pub struct TableOutputData {
+    hash: String,
+    pairs: Vec<(char, char)>,
+}
+
+impl TableInputData {
+    pub fn new(passphrase: &str, scripts: &[Script], allow_self_mapping: bool) -> Self {
+        TableInputData {
+            passphrase: passphrase.to_string(),
+            scripts: scripts.to_vec(),
+            allow_self_mapping,
+        }
+    }
+
+    pub fn get_passphrase(&self) -> &str {
+        &self.passphrase
+    }
+
+    pub fn get_scripts(&self) -> &[Script] {
+        &self.scripts
+    }
+
+    pub fn get_allow_self_mapping(&self) -> bool {
+        self.allow_self_mapping
+    }
+}
+
+impl TableOutputData {
+    pub fn new(hash: &str, pairs: Vec<(char, char)>) -> Self {
+        TableOutputData {
+            hash: hash.to_string(),
+            pairs,
+        }
+    }
+
+    pub fn get_hash(&self) -> &str {
+        &self.hash
+    }
+
+    pub fn get_pairs(&self) -> &[(char, char)] {
+        &self.pairs
+    }
+}
+
+pub struct TableInteractor {
+    registry: Arc<TableRegistry>,
+    secret: Vec<u8>,
+}
+
+impl TableInteractor {
+    pub fn new() -> TableInteractor {
+        Self::with_registry(Arc::new(TableRegistry::new()))
+    }
+
+    pub fn with_registry(registry: Arc<TableRegistry>) -> TableInteractor {
+        TableInteractor {
+            registry,
+            secret: Vec::new(),
+        }
+    }
+
+    //表の識別子を合言葉から当てられないようにする秘密鍵
+    pub fn with_secret(mut self, secret: &[u8]) -> Self {
+        self.secret = secret.to_vec();
+        self
+    }
+}
+
+impl Default for TableInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableInputPort for TableInteractor {
+    fn generate(&self, input_data: TableInputData) -> Result<TableOutputData, UsecaseError> {
+        let keyed = KeyedTable::generate(
+            input_data.get_passphrase(),
+            input_data.get_scripts(),
+            input_data.get_allow_self_mapping(),
+            &self.secret,
+        )?;
+        let hash = keyed.hash().to_string();
+        let table = self.registry.register_keyed(&hash, keyed.into_table())?;
+        Ok(TableOutputData::new(&hash, table.pairs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let registry = Arc::new(TableRegistry::new());
+        let table_port = TableInteractor::with_registry(registry.clone());
+        let table_input_data = TableInputData::new("zebras", &[Script::Latin], true);
+        let result = table_port.generate(table_input_data);
+
+        assert!(result.is_ok());
+        let output = result.unwrap();
+        assert_eq!(output.get_pairs().len(), 26);
+        assert_eq!(output.get_pairs()[0], ('A', 'Z'));
+        assert!(registry.get(output.get_hash()).is_some());
+    }
+
+    #[test]
+    fn test_generate_empty_passphrase() {
+        let table_port = TableInteractor::new();
+        let result = table_port.generate(TableInputData::new("", &[Script::Latin], true));
+        assert!(result.is_err());
+    }
+}
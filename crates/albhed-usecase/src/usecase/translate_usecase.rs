@@ -0,0 +1,650 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::domain::cipher_plugin::convert_except;
+use crate::domain::entities::{AlBhedText, OriginalText, Script, SubstitutionTable};
+use crate::domain::error::EntityError;
+use crate::domain::lint::LintFinding;
+use crate::domain::markup::{Markup, TagHandling};
+use crate::domain::parallel::{self, DEFAULT_CHUNK_SIZE};
+use crate::domain::pipeline::{Phase, Pipeline, Shield, StageReport};
+use crate::domain::pronunciation::{pronounce, WordPronunciation};
+use crate::domain::protection::{find_entities, EntityKind, ProtectedSpan};
+use crate::domain::ssml::{render_ssml, SsmlOptions};
+use crate::domain::table_registry::{TableKind, TableRegistry, DEFAULT_TABLE};
+use crate::usecase::error::UsecaseError;
+use crate::usecase::input_lint::{lint_input, LintMode};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    //平文からアルベド語へ
+    Encode,
+    //アルベド語から平文へ
+    Decode,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CaseMode {
+    //表にある文字だけを変換する
+    #[default]
+    Exact,
+    //小文字も変換し、大文字・小文字はそのまま残す
+    Preserve,
+}
+
+pub trait TranslateInputPort {
+    fn translate(
+        &self,
+        input_data: TranslateInputData,
+    ) -> Result<TranslateOutputData, UsecaseError>;
+}
+
+#[derive(Clone, Default)]
+pub struct TranslationOptions {
+    table: Option<String>,
+    case: CaseMode,
+    scripts: Option<Vec<Script>>,
+    protections: Option<Vec<EntityKind>>,
+    excluded: Vec<Range<usize>>,
+    markup: Option<TagHandling>,
+    lint_mode: Option<LintMode>,
+    pipeline: Option<Pipeline>,
+    primers: Option<Vec<char>>,
+    pronunciation: bool,
+    ssml: Option<SsmlOptions>,
+}
+
+impl TranslationOptions {
+    pub fn builder() -> TranslationOptionsBuilder {
+        TranslationOptionsBuilder::default()
+    }
+
+    pub fn get_table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    pub fn get_case(&self) -> CaseMode {
+        self.case
+    }
+
+    pub fn get_scripts(&self) -> Option<&[Script]> {
+        self.scripts.as_deref()
+    }
+
+    pub fn get_protections(&self) -> Option<&[EntityKind]> {
+        self.protections.as_deref()
+    }
+
+    pub fn get_excluded(&self) -> &[Range<usize>] {
+        &self.excluded
+    }
+
+    pub fn get_markup(&self) -> Option<TagHandling> {
+        self.markup
+    }
+
+    pub fn get_lint_mode(&self) -> Option<LintMode> {
+        self.lint_mode
+    }
+
+    pub fn get_pipeline(&self) -> Option<&Pipeline> {
+        self.pipeline.as_ref()
+    }
+
+    pub fn get_primers(&self) -> Option<&[char]> {
+        self.primers.as_deref()
+    }
+
+    pub fn get_pronunciation(&self) -> bool {
+        self.pronunciation
+    }
+
+    pub fn get_ssml(&self) -> Option<&SsmlOptions> {
+        self.ssml.as_ref()
+    }
+}
+
+#[derive(Default)]
+pub struct TranslationOptionsBuilder {
+    options: TranslationOptions,
+}
+
+impl TranslationOptionsBuilder {
+    //登録済みの表かプラグインの名前。指定がなければ組み込みの表
+    pub fn with_table(mut self, table: &str) -> Self {
+        self.options.table = Some(table.to_string());
+        self
+    }
+
+    pub fn with_case(mut self, case: CaseMode) -> Self {
+        self.options.case = case;
+        self
+    }
+
+    //指定した文字の変換ルールだけを使う
+    pub fn with_scripts(mut self, scripts: &[Script]) -> Self {
+        self.options.scripts = Some(scripts.to_vec());
+        self
+    }
+
+    pub fn with_protections(mut self, protections: &[EntityKind]) -> Self {
+        self.options.protections = Some(protections.to_vec());
+        self
+    }
+
+    //変換しない範囲（文字位置）。タグの処理をしたあとのテキストでの位置
+    pub fn with_excluded(mut self, excluded: &[Range<usize>]) -> Self {
+        self.options.excluded = excluded.to_vec();
+        self
+    }
+
+    pub fn with_markup(mut self, markup: TagHandling) -> Self {
+        self.options.markup = Some(markup);
+        self
+    }
+
+    pub fn with_lint_mode(mut self, mode: LintMode) -> Self {
+        self.options.lint_mode = Some(mode);
+        self
+    }
+
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.options.pipeline = Some(pipeline);
+        self
+    }
+
+    //覚えた文字は変換せずに残す
+    pub fn with_primers(mut self, letters: &[char]) -> Self {
+        self.options.primers = Some(letters.to_vec());
+        self
+    }
+
+    pub fn with_pronunciation(mut self) -> Self {
+        self.options.pronunciation = true;
+        self
+    }
+
+    pub fn with_ssml(mut self, options: SsmlOptions) -> Self {
+        self.options.ssml = Some(options);
+        self
+    }
+
+    pub fn build(self) -> TranslationOptions {
+        self.options
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TranslationWarning {
+    //入力に紛らわしい文字があった
+    SuspiciousCharacter(LintFinding),
+    //この暗号やこの向きでは使えないので無視した指定
+    IgnoredOption(&'static str),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranslationMetadata {
+    table: String,
+    kind: TableKind,
+    scripts: Vec<Script>,
+    stage_reports: Vec<StageReport>,
+}
+
+impl TranslationMetadata {
+    pub fn get_table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn get_kind(&self) -> TableKind {
+        self.kind
+    }
+
+    //実際に変換に使った文字の種類。プラグインでは空
+    pub fn get_scripts(&self) -> &[Script] {
+        &self.scripts
+    }
+
+    pub fn get_stage_reports(&self) -> &[StageReport] {
+        &self.stage_reports
+    }
+}
+
+pub struct TranslateInputData {
+    text: String,
+    direction: Direction,
+    options: TranslationOptions,
+}
+
+pub struct TranslateOutputData {
+    text: String,
+    direction: Direction,
+    metadata: TranslationMetadata,
+    warnings: Vec<TranslationWarning>,
+    protected: Option<Vec<ProtectedSpan>>,
+    pronunciation: Option<Vec<WordPronunciation>>,
+    ssml: Option<String>,
+}
+
+impl TranslateInputData {
+    pub fn new(input: &str, direction: Direction, options: TranslationOptions) -> Self {
+        TranslateInputData {
+            text: input.to_string(),
+            direction,
+            options,
+        }
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn get_options(&self) -> &TranslationOptions {
+        &self.options
+    }
+}
+
+impl TranslateOutputData {
+    pub fn new(output: &str, direction: Direction, metadata: TranslationMetadata) -> Self {
+        TranslateOutputData {
+            text: output.to_string(),
+            direction,
+            metadata,
+            warnings: Vec::new(),
+            protected: None,
+            pronunciation: None,
+            ssml: None,
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<TranslationWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn with_protected(mut self, protected: Vec<ProtectedSpan>) -> Self {
+        self.protected = Some(protected);
+        self
+    }
+
+    pub fn with_pronunciation(mut self, pronunciation: Vec<WordPronunciation>) -> Self {
+        self.pronunciation = Some(pronunciation);
+        self
+    }
+
+    pub fn with_ssml(mut self, ssml: String) -> Self {
+        self.ssml = Some(ssml);
+        self
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn get_metadata(&self) -> &TranslationMetadata {
+        &self.metadata
+    }
+
+    pub fn get_warnings(&self) -> &[TranslationWarning] {
+        &self.warnings
+    }
+
+    pub fn get_lint_findings(&self) -> Vec<LintFinding> {
+        self.warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                TranslationWarning::SuspiciousCharacter(finding) => Some(finding.clone()),
+                TranslationWarning::IgnoredOption(_) => None,
+            })
+            .collect()
+    }
+
+    //保護指定があったときだけSomeになる
+    pub fn get_protected(&self) -> Option<&[ProtectedSpan]> {
+        self.protected.as_deref()
+    }
+
+    pub fn get_pronunciation(&self) -> Option<&[WordPronunciation]> {
+        self.pronunciation.as_deref()
+    }
+
+    pub fn get_ssml(&self) -> Option<&str> {
+        self.ssml.as_deref()
+    }
+}
+
+//これより大きい入力は塊に分けて並列に変換する（バイト）
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 4 * 1024 * 1024;
+
+pub struct TranslateInteractor {
+    registry: Arc<TableRegistry>,
+    lint_mode: LintMode,
+    pipeline: Pipeline,
+    parallel_threshold: usize,
+}
+
+impl TranslateInteractor {
+    pub fn new() -> TranslateInteractor {
+        Self::with_registry(Arc::new(TableRegistry::new()))
+    }
+
+    pub fn with_registry(registry: Arc<TableRegistry>) -> TranslateInteractor {
+        TranslateInteractor {
+            registry,
+            lint_mode: LintMode::Off,
+            pipeline: Pipeline::new(),
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
+        }
+    }
+
+    //リクエストで指定がないときの入力検査
+    pub fn with_lint_mode(mut self, mode: LintMode) -> Self {
+        self.lint_mode = mode;
+        self
+    }
+
+    //リクエストで指定がないときの前後処理
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    //保護する範囲がなければ、この大きさ（バイト）以上の入力を並列に変換する
+    pub fn with_parallel_threshold(mut self, bytes: usize) -> Self {
+        self.parallel_threshold = bytes;
+        self
+    }
+}
+
+impl Default for TranslateInteractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TranslateInputPort for TranslateInteractor {
+    fn translate(
+        &self,
+        input_data: TranslateInputData,
+    ) -> Result<TranslateOutputData, UsecaseError> {
+        let options = input_data.get_options();
+        let direction = input_data.get_direction();
+        let lint_mode = options.get_lint_mode().unwrap_or(self.lint_mode);
+        let (linted, lint_findings) = lint_input(input_data.get_text(), lint_mode)?;
+        let mut warnings: Vec<TranslationWarning> = lint_findings
+            .into_iter()
+            .map(TranslationWarning::SuspiciousCharacter)
+            .collect();
+        let (text, mut excluded) = match options.get_markup() {
+            Some(handling) => {
+                let markup = Markup::parse(&linted)?;
+                (markup.text(handling), markup.excluded(handling))
+            }
+            None => (linted, Vec::new()),
+        };
+
+        let protected = options
+            .get_protections()
+            .map(|protections| find_entities(&text, protections));
+        if let Some(spans) = &protected {
+            excluded.extend(spans.iter().map(|span| span.start()..span.end()));
+        }
+        excluded.extend(options.get_excluded().iter().cloned());
+
+        //変換しない範囲は目印に置き換え、前後の処理でも変えない
+        let (shield, shielded) = Shield::new(&text, &excluded);
+        let pipeline = options.get_pipeline().unwrap_or(&self.pipeline);
+        let (text, mut stage_reports) = pipeline.run_shielded(Phase::Pre, &shielded, &shield)?;
+        if text.is_empty() {
+            return Err(EntityError::EmptyText.into());
+        }
+        let excluded = shield.positions(&text);
+
+        let name = options.get_table().unwrap_or(DEFAULT_TABLE);
+        let plugin = options
+            .get_table()
+            .and_then(|name| self.registry.plugin(name));
+        let (translated, kind, scripts) = match plugin {
+            Some(plugin) => {
+                //プラグインは表を持たないので、表に対する指定は使わない
+                for (ignored, option) in [
+                    (options.get_scripts().is_some(), "scripts"),
+                    (options.get_primers().is_some(), "primers"),
+                    (options.get_case() != CaseMode::Exact, "case"),
+                ] {
+                    if ignored {
+                        warnings.push(TranslationWarning::IgnoredOption(option));
+                    }
+                }
+                let translated = convert_except(&text, &excluded, |segment| match direction {
+                    Direction::Encode => plugin.encode(segment),
+                    Direction::Decode => plugin.decode(segment),
+                })?;
+                (translated, TableKind::Plugin, Vec::new())
+            }
+            None => {
+                //表を選び、文字の種類・覚えた文字・大文字小文字の指定を反映する
+                let registered;
+                let mut table = match options.get_table() {
+                    Some(name) => {
+                        registered = self
+                            .registry
+                            .get(name)
+                            .ok_or_else(|| UsecaseError::UnknownTable(name.to_string()))?;
+                        Cow::Borrowed(registered.as_ref())
+                    }
+                    None => Cow::Borrowed(SubstitutionTable::albhed()),
+                };
+                if let Some(scripts) = options.get_scripts() {
+                    let languages: Vec<_> = scripts.iter().map(Script::language).collect();
+                    table = Cow::Owned(table.restricted_to(&languages));
+                }
+                if let Some(letters) = options.get_primers() {
+                    table = Cow::Owned(table.without_plaintext(letters));
+                }
+                if options.get_case() == CaseMode::Preserve {
+                    table = Cow::Owned(table.case_insensitive());
+                }
+                let parallel = excluded.is_empty() && text.len() >= self.parallel_threshold;
+                let translated = match direction {
+                    Direction::Encode if parallel => {
+                        parallel::encode(&table, &text, DEFAULT_CHUNK_SIZE).into_owned()
+                    }
+                    Direction::Decode if parallel => {
+                        parallel::decode(&table, &text, DEFAULT_CHUNK_SIZE).into_owned()
+                    }
+                    Direction::Encode => OriginalText::new(text.as_str())?
+                        .encode_except(&table, &excluded)
+                        .into_text()
+                        .into_owned(),
+                    Direction::Decode => AlBhedText::new(text.as_str())?
+                        .decode_except(&table, &excluded)
+                        .into_text()
+                        .into_owned(),
+                };
+                let kind = self
+                    .registry
+                    .info(name)
+                    .map_or(TableKind::Builtin, |info| info.kind());
+                let scripts = table.languages().iter().map(|l| l.script()).collect();
+                (translated, kind, scripts)
+            }
+        };
+        let (translated, post_reports) =
+            pipeline.run_shielded(Phase::Post, &translated, &shield)?;
+        stage_reports.extend(post_reports);
+        let (translated, restored) = shield.restore(&translated);
+
+        //読み方とSSMLはアルベド語の出力にだけ付ける
+        let speech = direction == Direction::Encode;
+        if !speech && options.get_pronunciation() {
+            warnings.push(TranslationWarning::IgnoredOption("pronunciation"));
+        }
+        if !speech && options.get_ssml().is_some() {
+            warnings.push(TranslationWarning::IgnoredOption("ssml"));
+        }
+
+        let metadata = TranslationMetadata {
+            table: name.to_string(),
+            kind,
+            scripts,
+            stage_reports,
+        };
+        let mut output_data =
+            TranslateOutputData::new(&translated, direction, metadata).with_warnings(warnings);
+        if speech && options.get_pronunciation() {
+            output_data = output_data.with_pronunciation(pronounce(&translated));
+        }
+        if let Some(ssml) = options.get_ssml().filter(|_| speech) {
+            //保護した部分は目印を戻した位置から出力での位置を求める
+            let spans: Vec<Range<usize>> = protected
+                .iter()
+                .flatten()
+                .filter_map(|span| shield.locate(&(span.start()..span.end()), &restored))
+                .collect();
+            output_data = output_data.with_ssml(render_ssml(&translated, &spans, ssml));
+        }
+        Ok(match protected {
+            Some(spans) => output_data.with_protected(spans),
+            None => output_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(
+        text: &str,
+        direction: Direction,
+        options: TranslationOptions,
+    ) -> TranslateOutputData {
+        let translate_port = TranslateInteractor::new();
+        translate_port
+            .translate(TranslateInputData::new(text, direction, options))
+            .unwrap()
+    }
+
+    #[test]
+    #[cfg(all(feature = "english", feature = "japanese"))]
+    fn test_translate_both_directions() {
+        let options = TranslationOptions::builder().build();
+        let encoded = translate("HELLO ゆうな", Direction::Encode, options.clone());
+        assert_eq!(encoded.get_text(), "RAMMU ヲフハ");
+        assert_eq!(encoded.get_direction(), Direction::Encode);
+        assert_eq!(encoded.get_metadata().get_table(), DEFAULT_TABLE);
+        assert_eq!(
+            encoded.get_metadata().get_scripts(),
+            [Script::Latin, Script::Kana]
+        );
+
+        let decoded = translate(encoded.get_text(), Direction::Decode, options);
+        assert_eq!(decoded.get_text(), "HELLO ゆうな");
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_translate_with_options() {
+        let options = TranslationOptions::builder()
+            .with_case(CaseMode::Preserve)
+            .with_scripts(&[Script::Latin])
+            .with_protections(&[EntityKind::Mention])
+            .build();
+        let encoded = translate("Hello @Rikku ゆうな", Direction::Encode, options);
+
+        assert_eq!(encoded.get_text(), "Rammu @Rikku ゆうな");
+        assert_eq!(encoded.get_metadata().get_scripts(), [Script::Latin]);
+        assert_eq!(encoded.get_protected().unwrap().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "english")]
+    fn test_translate_ssml_keeps_protected_offsets() {
+        //前の暗号文が保護したURLと同じ文字列になる
+        let url = "HTTPS://EXAMPLE.COM/A";
+        let plain = SubstitutionTable::albhed().decode(url);
+        let options = TranslationOptions::builder()
+            .with_protections(&[EntityKind::Url])
+            .with_ssml(SsmlOptions::new("en-US"))
+            .build();
+        let encoded = translate(&format!("{} {}", plain, url), Direction::Encode, options);
+
+        assert_eq!(encoded.get_text(), format!("{} {}", url, url));
+        assert_eq!(encoded.get_protected().unwrap().len(), 1);
+        let ssml = encoded.get_ssml().unwrap();
+        assert_eq!(ssml.matches("<lang").count(), 1);
+        assert!(ssml.ends_with(&format!(" <lang xml:lang=\"en-US\">{}</lang></speak>", url)));
+    }
+
+    #[test]
+    #[cfg(feature = "japanese")]
+    fn test_translate_ssml_with_composed_voicing() {
+        //濁点が合成されて、出力は入力より2文字短くなる
+        let options = TranslationOptions::builder()
+            .with_protections(&[EntityKind::Url])
+            .with_ssml(SsmlOptions::new("en-US"))
+            .build();
+        let text = "か\u{3099}か\u{3099} https://example.com/a すごい";
+        let encoded = translate(text, Direction::Encode, options);
+
+        assert_eq!(encoded.get_text().chars().count(), text.chars().count() - 2);
+        let ssml = encoded.get_ssml().unwrap();
+        assert!(ssml.contains("<lang xml:lang=\"en-US\">https://example.com/a</lang>"));
+        assert!(ssml.ends_with(&format!(
+            "</lang> {}</speak>",
+            SubstitutionTable::albhed().encode("すごい")
+        )));
+    }
+
+    #[test]
+    fn test_translate_warnings() {
+        let options = TranslationOptions::builder()
+            .with_lint_mode(LintMode::Report)
+            .with_pronunciation()
+            .build();
+        let decoded = translate("\u{0410}RAMMU", Direction::Decode, options);
+
+        assert_eq!(decoded.get_lint_findings().len(), 1);
+        assert_eq!(
+            decoded.get_warnings()[1],
+            TranslationWarning::IgnoredOption("pronunciation")
+        );
+        assert!(decoded.get_pronunciation().is_none());
+    }
+
+    #[test]
+    fn test_translate_parallel() {
+        let text = "HELLO か\u{3099} CAFE\u{0301} ゆうな\n".repeat(1000);
+        let options = TranslationOptions::builder()
+            .with_case(CaseMode::Preserve)
+            .build();
+        let sequential = translate(&text, Direction::Encode, options.clone());
+        let parallel = TranslateInteractor::new()
+            .with_parallel_threshold(1)
+            .translate(TranslateInputData::new(&text, Direction::Encode, options))
+            .unwrap();
+
+        assert_eq!(parallel.get_text(), sequential.get_text());
+    }
+
+    #[test]
+    fn test_translate_empty() {
+        let translate_port = TranslateInteractor::new();
+        let input_data =
+            TranslateInputData::new("", Direction::Encode, TranslationOptions::default());
+
+        assert_eq!(
+            translate_port.translate(input_data).err().unwrap().code(),
+            "empty_text"
+        );
+    }
+}
@@ -0,0 +1,12 @@
+#!/bin/sh
+# SSML 1.1 のスキーマを W3C から取ってきて置き換える
+set -eu
+
+cd "$(dirname "$0")"
+curl -fsSL -o synthesis.xsd https://www.w3.org/TR/speech-synthesis11/synthesis.xsd
+curl -fsSL -o synthesis-core.xsd https://www.w3.org/TR/speech-synthesis11/synthesis-core.xsd
+curl -fsSL -o xml.xsd https://www.w3.org/2001/xml.xsd
+
+# xmllint --nonet で読めるように、xml.xsd は手元のものを使う
+sed -i 's#schemaLocation="http://www.w3.org/2001/xml.xsd"#schemaLocation="xml.xsd"#' \
+    synthesis.xsd synthesis-core.xsd
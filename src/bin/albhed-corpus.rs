@@ -0,0 +1,23 @@
+use std::error::Error;
+use std::process::ExitCode;
+
+use albhed_translator_service::infrastracture::corpus;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match corpus::run(&args) {
+        Ok(summary) => {
+            println!("{}", summary);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            let mut source = error.source();
+            while let Some(cause) = source {
+                eprintln!("  caused by: {}", cause);
+                source = cause.source();
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1 +1,4 @@
+pub mod corpus;
+pub mod stream;
+pub mod wasm_plugin;
 pub mod web;
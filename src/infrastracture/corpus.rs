@@ -0,0 +1,312 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::usecase::corpus_usecase::{
+    CorpusInputData, CorpusInputPort, CorpusInteractor, ParallelPair, Split,
+};
+use crate::usecase::error::UsecaseError;
+
+const USAGE: &str = "Usage: albhed-corpus --input <file>... --output <dir> [--field <name>] \
+[--seed <n>] [--primer-rate <0-1>] [--case-noise <0-1>] [--names <file>] [--name-rate <0-1>] \
+[--split <train,dev,test>]";
+
+#[derive(Debug, Error)]
+pub enum CorpusError {
+    #[error("Missing value for {0}\n{USAGE}")]
+    MissingValue(String),
+    #[error("Unknown argument: {0}\n{USAGE}")]
+    UnknownArgument(String),
+    #[error("Missing --output\n{USAGE}")]
+    MissingOutput,
+    #[error("Missing --input\n{USAGE}")]
+    MissingInput,
+    #[error("Invalid value for {arg}: {value}")]
+    InvalidValue { arg: String, value: String },
+    #[error("--split needs three ratios\n{USAGE}")]
+    SplitArity,
+    #[error("Failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to write {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to create {}", path.display())]
+    CreateDir {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Invalid JSON at {}:{line}", path.display())]
+    InvalidJsonLine {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Missing field {field} at {}:{line}", path.display())]
+    MissingField {
+        field: String,
+        path: PathBuf,
+        line: usize,
+    },
+    #[error("Failed to serialize response")]
+    Serialization(#[source] serde_json::Error),
+    #[error(transparent)]
+    Usecase(#[from] UsecaseError),
+}
+
+#[derive(Serialize)]
+struct PairRecord<'a> {
+    plaintext: &'a str,
+    ciphertext: &'a str,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    primers: String,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    names: &'a [String],
+}
+
+impl<'a> From<&'a ParallelPair> for PairRecord<'a> {
+    fn from(pair: &'a ParallelPair) -> Self {
+        PairRecord {
+            plaintext: pair.plaintext(),
+            ciphertext: pair.ciphertext(),
+            primers: pair.primers().iter().collect(),
+            names: pair.names(),
+        }
+    }
+}
+
+//コマンドライン引数を読み、コーパスを変換して split ごとのJSONLに書き出す
+pub fn run(args: &[String]) -> Result<String, CorpusError> {
+    let mut inputs = Vec::new();
+    let mut output = None;
+    let mut field = "text".to_string();
+    let mut seed = 0;
+    let mut primer_rate = 0.0;
+    let mut case_noise = 0.0;
+    let mut names = Vec::new();
+    let mut name_rate = 0.0;
+    let mut split = [0.8, 0.1, 0.1];
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| CorpusError::MissingValue(arg.clone()))
+        };
+        match arg.as_str() {
+            "--input" => inputs.push(PathBuf::from(value()?)),
+            "--output" => output = Some(PathBuf::from(value()?)),
+            "--field" => field = value()?,
+            "--seed" => seed = parse_number(arg, &value()?)?,
+            "--primer-rate" => primer_rate = parse_number(arg, &value()?)?,
+            "--case-noise" => case_noise = parse_number(arg, &value()?)?,
+            "--names" => names = read_lines(Path::new(&value()?))?,
+            "--name-rate" => name_rate = parse_number(arg, &value()?)?,
+            "--split" => {
+                let ratios = value()?
+                    .split(',')
+                    .map(|ratio| parse_number(arg, ratio))
+                    .collect::<Result<Vec<f64>, CorpusError>>()?;
+                split = ratios.try_into().map_err(|_| CorpusError::SplitArity)?;
+            }
+            _ => return Err(CorpusError::UnknownArgument(arg.clone())),
+        }
+    }
+    let output = output.ok_or(CorpusError::MissingOutput)?;
+    if inputs.is_empty() {
+        return Err(CorpusError::MissingInput);
+    }
+
+    let mut texts = Vec::new();
+    for input in &inputs {
+        texts.extend(read_texts(input, &field)?);
+    }
+    let corpus_input_data = CorpusInputData::new(texts, seed)
+        .with_primer_rate(primer_rate)
+        .with_case_noise(case_noise)
+        .with_names(&names, name_rate)
+        .with_split(split[0], split[1], split[2]);
+    let generated = CorpusInteractor::new().generate(corpus_input_data)?;
+    write_pairs(&output, generated.get_pairs())?;
+
+    let counts: Vec<String> = Split::ALL
+        .iter()
+        .map(|split| {
+            let count = generated
+                .get_pairs()
+                .iter()
+                .filter(|pair| pair.split() == *split)
+                .count();
+            format!("{} {}", split.name(), count)
+        })
+        .collect();
+    Ok(format!(
+        "Wrote {} pairs ({})",
+        generated.get_pairs().len(),
+        counts.join(", ")
+    ))
+}
+
+//.jsonl は各行のオブジェクトから field の文字列を、それ以外は各行をそのまま読む
+pub fn read_texts(path: &Path, field: &str) -> Result<Vec<String>, CorpusError> {
+    let lines = read_lines(path)?;
+    if path
+        .extension()
+        .is_none_or(|extension| extension != "jsonl")
+    {
+        return Ok(lines);
+    }
+    lines
+        .iter()
+        .enumerate()
+        .map(|(k, line)| {
+            let value: serde_json::Value =
+                serde_json::from_str(line).map_err(|source| CorpusError::InvalidJsonLine {
+                    path: path.to_path_buf(),
+                    line: k + 1,
+                    source,
+                })?;
+            value[field]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| CorpusError::MissingField {
+                    field: field.to_string(),
+                    path: path.to_path_buf(),
+                    line: k + 1,
+                })
+        })
+        .collect()
+}
+
+//train.jsonl / dev.jsonl / test.jsonl に書き出す
+pub fn write_pairs(directory: &Path, pairs: &[ParallelPair]) -> Result<(), CorpusError> {
+    fs::create_dir_all(directory).map_err(|source| CorpusError::CreateDir {
+        path: directory.to_path_buf(),
+        source,
+    })?;
+    for split in Split::ALL {
+        let mut body = String::new();
+        for pair in pairs.iter().filter(|pair| pair.split() == split) {
+            let record = serde_json::to_string(&PairRecord::from(pair))
+                .map_err(CorpusError::Serialization)?;
+            body.push_str(&record);
+            body.push('\n');
+        }
+        let path = directory.join(format!("{}.jsonl", split.name()));
+        fs::write(&path, body).map_err(|source| CorpusError::Write {
+            path: path.clone(),
+            source,
+        })?;
+    }
+    Ok(())
+}
+
+fn read_lines(path: &Path) -> Result<Vec<String>, CorpusError> {
+    let content = fs::read_to_string(path).map_err(|source| CorpusError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, CorpusError> {
+    value.trim().parse().map_err(|_| CorpusError::InvalidValue {
+        arg: arg.to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("albhed-corpus-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_run_writes_splits() {
+        let directory = temp_dir("run");
+        let txt = directory.join("corpus.txt");
+        let jsonl = directory.join("corpus.jsonl");
+        fs::write(&txt, "hello\n\nworld\n").unwrap();
+        fs::write(&jsonl, "{\"line\": \"rikku\"}\n").unwrap();
+        let output = directory.join("out");
+
+        let summary = run(&args(&[
+            "--input",
+            txt.to_str().unwrap(),
+            "--input",
+            jsonl.to_str().unwrap(),
+            "--field",
+            "line",
+            "--output",
+            output.to_str().unwrap(),
+            "--split",
+            "1,0,0",
+        ]))
+        .unwrap();
+
+        assert_eq!(summary, "Wrote 3 pairs (train 3, dev 0, test 0)");
+        assert_eq!(
+            fs::read_to_string(output.join("train.jsonl")).unwrap(),
+            "{\"plaintext\":\"hello\",\"ciphertext\":\"rammu\"}\n\
+             {\"plaintext\":\"world\",\"ciphertext\":\"funmt\"}\n\
+             {\"plaintext\":\"rikku\",\"ciphertext\":\"neggi\"}\n"
+        );
+        assert_eq!(fs::read_to_string(output.join("dev.jsonl")).unwrap(), "");
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_run_invalid_arguments() {
+        assert!(run(&args(&["--output", "out"]))
+            .unwrap_err()
+            .to_string()
+            .starts_with("Missing --input"));
+        assert!(run(&args(&["--bogus"]))
+            .unwrap_err()
+            .to_string()
+            .starts_with("Unknown argument: --bogus"));
+        assert!(run(&args(&[
+            "--input", "a.txt", "--output", "out", "--split", "1,0"
+        ]))
+        .unwrap_err()
+        .to_string()
+        .starts_with("--split needs three ratios"));
+    }
+
+    #[test]
+    fn test_read_texts_invalid_jsonl() {
+        let directory = temp_dir("jsonl");
+        let jsonl = directory.join("corpus.jsonl");
+        fs::write(&jsonl, "{\"text\": \"A\"}\n{\"other\": 1}\n").unwrap();
+
+        let error = read_texts(&jsonl, "text").unwrap_err().to_string();
+        assert!(error.starts_with("Missing field text at"), "{}", error);
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,543 @@
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock};
+use std::task::{ready, Context, Poll};
+
+use futures::io::AsyncRead;
+use futures::stream::Stream;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::domain::entities::SubstitutionTable;
+use crate::usecase::translate_usecase::Direction;
+
+//一度に読み書きするバイト数
+const BUFFER_SIZE: usize = 8 * 1024;
+//次の入力で続くかもしれない末尾のクラスタをこれ以上は持ち越さない
+const MAX_PENDING: usize = 4 * 1024;
+
+static ALBHED_TABLE: LazyLock<Arc<SubstitutionTable>> =
+    LazyLock::new(|| Arc::new(SubstitutionTable::albhed().clone()));
+
+//バイト列を少しずつ受け取って変換する
+//途中で切れたUTF-8と、結合文字が続くかもしれない末尾の書記素クラスタは次の入力まで持ち越す
+pub struct StreamTranslator {
+    table: Arc<SubstitutionTable>,
+    direction: Direction,
+    pending: Vec<u8>,
+}
+
+impl StreamTranslator {
+    pub fn new(direction: Direction) -> Self {
+        StreamTranslator {
+            table: ALBHED_TABLE.clone(),
+            direction,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn with_table(mut self, table: Arc<SubstitutionTable>) -> Self {
+        self.table = table;
+        self
+    }
+
+    //確定した部分を変換して output に追加する
+    pub fn feed(&mut self, input: &[u8], output: &mut String) -> io::Result<()> {
+        self.pending.extend_from_slice(input);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        let text = std::str::from_utf8(&self.pending[..valid]).unwrap();
+        let end = match text.grapheme_indices(true).next_back() {
+            Some((start, _)) if self.pending.len() - start <= MAX_PENDING => start,
+            _ => valid,
+        };
+        self.convert(&text[..end], output);
+        self.pending.drain(..end);
+        Ok(())
+    }
+
+    //持ち越していた残りを変換する。UTF-8が途中で切れていればエラー
+    pub fn finish(&mut self, output: &mut String) -> io::Result<()> {
+        let text = std::str::from_utf8(&self.pending)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        self.convert(text, output);
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn convert(&self, text: &str, output: &mut String) {
+        let converted = match self.direction {
+            Direction::Encode => self.table.encode(text),
+            Direction::Decode => self.table.decode(text),
+        };
+        output.push_str(&converted);
+    }
+}
+
+pub struct TranslateReader<R> {
+    inner: R,
+    translator: StreamTranslator,
+    buffer: Vec<u8>,
+    output: String,
+    position: usize,
+    done: bool,
+}
+
+impl<R: Read> TranslateReader<R> {
+    pub fn new(inner: R, direction: Direction) -> Self {
+        TranslateReader {
+            inner,
+            translator: StreamTranslator::new(direction),
+            buffer: vec![0; BUFFER_SIZE],
+            output: String::new(),
+            position: 0,
+            done: false,
+        }
+    }
+
+    pub fn with_table(mut self, table: Arc<SubstitutionTable>) -> Self {
+        self.translator = self.translator.with_table(table);
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for TranslateReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.output.len() {
+            if self.done {
+                return Ok(0);
+            }
+            self.output.clear();
+            self.position = 0;
+            let n = self.inner.read(&mut self.buffer)?;
+            if n == 0 {
+                self.done = true;
+                self.translator.finish(&mut self.output)?;
+            } else {
+                self.translator.feed(&self.buffer[..n], &mut self.output)?;
+            }
+        }
+        let remaining = &self.output.as_bytes()[self.position..];
+        let n = buf.len().min(remaining.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+//最後まで書いたら finish を呼ぶ。呼ばずに捨てたときは持ち越した分をできるだけ書き出す
+pub struct TranslateWriter<W: Write> {
+    inner: Option<W>,
+    translator: StreamTranslator,
+    output: String,
+}
+
+impl<W: Write> TranslateWriter<W> {
+    pub fn new(inner: W, direction: Direction) -> Self {
+        TranslateWriter {
+            inner: Some(inner),
+            translator: StreamTranslator::new(direction),
+            output: String::new(),
+        }
+    }
+
+    pub fn with_table(mut self, table: Arc<SubstitutionTable>) -> Self {
+        self.translator.table = table;
+        self
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_remaining()?;
+        let mut inner = self.inner.take().unwrap();
+        inner.flush()?;
+        Ok(inner)
+    }
+
+    fn write_remaining(&mut self) -> io::Result<()> {
+        self.output.clear();
+        self.translator.finish(&mut self.output)?;
+        self.inner
+            .as_mut()
+            .unwrap()
+            .write_all(self.output.as_bytes())
+    }
+}
+
+impl<W: Write> Write for TranslateWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let inner = self.inner.as_mut().unwrap();
+        for chunk in buf.chunks(BUFFER_SIZE) {
+            self.output.clear();
+            self.translator.feed(chunk, &mut self.output)?;
+            inner.write_all(self.output.as_bytes())?;
+        }
+        Ok(buf.len())
+    }
+
+    //持ち越したクラスタは続きが来るかもしれないので、ここでは書き出さない
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for TranslateWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_remaining();
+        }
+    }
+}
+
+pub struct EncodeReader<R> {
+    reader: TranslateReader<R>,
+}
+
+impl<R: Read> EncodeReader<R> {
+    pub fn new(inner: R) -> Self {
+        EncodeReader {
+            reader: TranslateReader::new(inner, Direction::Encode),
+        }
+    }
+
+    pub fn with_table(self, table: Arc<SubstitutionTable>) -> Self {
+        EncodeReader {
+            reader: self.reader.with_table(table),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: Read> Read for EncodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+pub struct DecodeReader<R> {
+    reader: TranslateReader<R>,
+}
+
+impl<R: Read> DecodeReader<R> {
+    pub fn new(inner: R) -> Self {
+        DecodeReader {
+            reader: TranslateReader::new(inner, Direction::Decode),
+        }
+    }
+
+    pub fn with_table(self, table: Arc<SubstitutionTable>) -> Self {
+        DecodeReader {
+            reader: self.reader.with_table(table),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+pub struct EncodeWriter<W: Write> {
+    writer: TranslateWriter<W>,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    pub fn new(inner: W) -> Self {
+        EncodeWriter {
+            writer: TranslateWriter::new(inner, Direction::Encode),
+        }
+    }
+
+    pub fn with_table(self, table: Arc<SubstitutionTable>) -> Self {
+        EncodeWriter {
+            writer: self.writer.with_table(table),
+        }
+    }
+
+    pub fn finish(self) -> io::Result<W> {
+        self.writer.finish()
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+pub struct DecodeWriter<W: Write> {
+    writer: TranslateWriter<W>,
+}
+
+impl<W: Write> DecodeWriter<W> {
+    pub fn new(inner: W) -> Self {
+        DecodeWriter {
+            writer: TranslateWriter::new(inner, Direction::Decode),
+        }
+    }
+
+    pub fn with_table(self, table: Arc<SubstitutionTable>) -> Self {
+        DecodeWriter {
+            writer: self.writer.with_table(table),
+        }
+    }
+
+    pub fn finish(self) -> io::Result<W> {
+        self.writer.finish()
+    }
+}
+
+impl<W: Write> Write for DecodeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+//バイト列のストリームを、変換した文字列のストリームにする
+pub struct TranslateStream<S> {
+    inner: S,
+    translator: StreamTranslator,
+    done: bool,
+}
+
+impl<S> TranslateStream<S> {
+    pub fn new(inner: S, direction: Direction) -> Self {
+        TranslateStream {
+            inner,
+            translator: StreamTranslator::new(direction),
+            done: false,
+        }
+    }
+
+    pub fn with_table(mut self, table: Arc<SubstitutionTable>) -> Self {
+        self.translator = self.translator.with_table(table);
+        self
+    }
+}
+
+impl<S, B> Stream for TranslateStream<S>
+where
+    S: Stream<Item = io::Result<B>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    type Item = io::Result<String>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            let mut output = String::new();
+            let result = match ready!(Pin::new(&mut this.inner).poll_next(cx)) {
+                Some(Ok(chunk)) => this.translator.feed(chunk.as_ref(), &mut output),
+                Some(Err(error)) => Err(error),
+                None => {
+                    this.done = true;
+                    this.translator.finish(&mut output)
+                }
+            };
+            if let Err(error) = result {
+                this.done = true;
+                return Poll::Ready(Some(Err(error)));
+            }
+            if !output.is_empty() {
+                return Poll::Ready(Some(Ok(output)));
+            }
+        }
+    }
+}
+
+pub struct TranslateAsyncReader<R> {
+    inner: R,
+    translator: StreamTranslator,
+    buffer: Vec<u8>,
+    output: String,
+    position: usize,
+    done: bool,
+}
+
+impl<R> TranslateAsyncReader<R> {
+    pub fn new(inner: R, direction: Direction) -> Self {
+        TranslateAsyncReader {
+            inner,
+            translator: StreamTranslator::new(direction),
+            buffer: vec![0; BUFFER_SIZE],
+            output: String::new(),
+            position: 0,
+            done: false,
+        }
+    }
+
+    pub fn with_table(mut self, table: Arc<SubstitutionTable>) -> Self {
+        self.translator = self.translator.with_table(table);
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for TranslateAsyncReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        while this.position == this.output.len() {
+            if this.done {
+                return Poll::Ready(Ok(0));
+            }
+            let n = ready!(Pin::new(&mut this.inner).poll_read(cx, &mut this.buffer))?;
+            this.output.clear();
+            this.position = 0;
+            if n == 0 {
+                this.done = true;
+                this.translator.finish(&mut this.output)?;
+            } else {
+                this.translator.feed(&this.buffer[..n], &mut this.output)?;
+            }
+        }
+        let remaining = &this.output.as_bytes()[this.position..];
+        let n = buf.len().min(remaining.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        this.position += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use futures::io::AsyncReadExt;
+    use futures::stream::{self, StreamExt, TryStreamExt};
+
+    use super::*;
+
+    //1バイトずつしか返さないので、UTF-8も結合文字もすべて途中で切れる
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    const PLAIN: &str = "HELLO か\u{3099}は\u{309A} CAFE\u{0301}\r\n👍\u{1F3FD} やっちまうか！？";
+    const ALBHED: &str = "RAMMU ダプ LYVA\u{0301}\r\n👍\u{1F3FD} タッヒヤフア！？";
+
+    #[test]
+    fn test_reader_with_split_input() {
+        let mut encoded = String::new();
+        EncodeReader::new(Trickle(PLAIN.as_bytes()))
+            .read_to_string(&mut encoded)
+            .unwrap();
+        assert_eq!(encoded, ALBHED);
+
+        let mut decoded = String::new();
+        DecodeReader::new(Trickle(encoded.as_bytes()))
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(
+            decoded,
+            "HELLO がぱ CAFE\u{0301}\r\n👍\u{1F3FD} やっちまうか！？"
+        );
+    }
+
+    #[test]
+    fn test_writer_with_split_input() {
+        let mut writer = EncodeWriter::new(Vec::new());
+        for byte in PLAIN.as_bytes() {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        let encoded = writer.finish().unwrap();
+        assert_eq!(String::from_utf8(encoded).unwrap(), ALBHED);
+
+        let mut output = Vec::new();
+        DecodeWriter::new(&mut output).write_all(b"RAMMU").unwrap();
+        assert_eq!(output, b"HELLO");
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut reader = EncodeReader::new(&b"AB\xFFCD"[..]);
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut writer = EncodeWriter::new(Vec::new());
+        writer
+            .write_all("あ".as_bytes().split_last().unwrap().1)
+            .unwrap();
+        assert_eq!(
+            writer.finish().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_pending_is_bounded() {
+        //結合文字が続いても、持ち越すのは MAX_PENDING まで
+        let text = format!("A{}", "\u{0301}".repeat(MAX_PENDING));
+        let mut translator = StreamTranslator::new(Direction::Encode);
+        let mut output = String::new();
+        for chunk in text.as_bytes().chunks(100) {
+            translator.feed(chunk, &mut output).unwrap();
+            assert!(translator.pending.len() <= MAX_PENDING + 100);
+        }
+        translator.finish(&mut output).unwrap();
+        assert_eq!(output, format!("Y{}", "\u{0301}".repeat(MAX_PENDING)));
+    }
+
+    #[test]
+    fn test_stream() {
+        let chunks = PLAIN
+            .as_bytes()
+            .chunks(3)
+            .map(|chunk| Ok::<_, io::Error>(chunk.to_vec()));
+        let translated = TranslateStream::new(stream::iter(chunks), Direction::Encode);
+        let encoded: Vec<String> = block_on(translated.try_collect()).unwrap();
+        assert_eq!(encoded.concat(), ALBHED);
+
+        let failing = stream::iter([Ok(b"AB".to_vec()), Err(io::Error::other("closed"))]);
+        let mut translated = TranslateStream::new(failing, Direction::Encode);
+        assert_eq!(block_on(translated.next()).unwrap().unwrap(), "Y");
+        assert!(block_on(translated.next()).unwrap().is_err());
+        assert!(block_on(translated.next()).is_none());
+    }
+
+    #[test]
+    fn test_async_reader() {
+        let mut reader = TranslateAsyncReader::new(PLAIN.as_bytes(), Direction::Encode);
+        let mut encoded = String::new();
+        block_on(reader.read_to_string(&mut encoded)).unwrap();
+        assert_eq!(encoded, ALBHED);
+    }
+}
@@ -0,0 +1,451 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::string::FromUtf8Error;
+use std::sync::Arc;
+
+use thiserror::Error;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+use crate::domain::cipher_plugin::CipherPlugin;
+use crate::domain::error::DomainError;
+use crate::domain::table_registry::TableRegistry;
+
+//プラグインのABI
+//  export "memory"                      : 線形メモリ
+//  export "alloc"  (len: i32) -> i32    : 入力を書き込む場所を確保する
+//  export "encode" (ptr: i32, len: i32) -> i64
+//  export "decode" (ptr: i32, len: i32) -> i64
+//入出力はUTF-8で、結果は (ptr << 32) | len を返す。負の値は入力を受け付けなかったことを表す
+//保護した部分（URLやメンションなど）は渡さない。保護した部分で区切られた前後は別々に呼ぶので、
+//前後の文字で変わる規則は区切りをまたいでは効かない
+//importは何も渡さないので、ファイルやネットワークには触れない
+
+type Source = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("Failed to read {path}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Invalid plugin file name: {0}")]
+    InvalidFileName(PathBuf),
+    #[error("Table {0} is already registered")]
+    NameTaken(String),
+    #[error("Failed to start plugin engine")]
+    Engine(#[source] Source),
+    #[error("Invalid plugin {plugin}")]
+    InvalidModule {
+        plugin: String,
+        #[source]
+        source: Source,
+    },
+    #[error("Plugin {0} must not import anything")]
+    ForbiddenImport(String),
+    #[error("Plugin {plugin} does not export {export}")]
+    MissingExport {
+        plugin: String,
+        export: &'static str,
+    },
+    #[error("Input is too large")]
+    InputTooLarge,
+    #[error("Output of {len} bytes exceeds the limit of {limit} bytes")]
+    OutputTooLarge { len: usize, limit: usize },
+    #[error("Output at {ptr}..{end} is outside linear memory of {size} bytes")]
+    OutputOutOfBounds { ptr: usize, end: usize, size: usize },
+    #[error("Trapped: {0}")]
+    Trap(Trap),
+    #[error("Runtime error")]
+    Runtime(#[source] Source),
+    #[error("Returned invalid UTF-8")]
+    InvalidUtf8(#[source] FromUtf8Error),
+}
+
+impl From<wasmtime::Error> for PluginError {
+    fn from(error: wasmtime::Error) -> Self {
+        match error.downcast_ref::<Trap>() {
+            Some(trap) => PluginError::Trap(*trap),
+            None => PluginError::Runtime(error.into()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PluginLimits {
+    fuel: u64,
+    memory: usize,
+    output: usize,
+}
+
+impl PluginLimits {
+    pub fn new() -> Self {
+        PluginLimits {
+            fuel: 10_000_000,
+            memory: 16 * 1024 * 1024,
+            output: 16 * 1024 * 1024,
+        }
+    }
+
+    //1回の呼び出しで実行できる命令の量
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    //線形メモリの上限（バイト）
+    pub fn with_memory(mut self, bytes: usize) -> Self {
+        self.memory = bytes;
+        self
+    }
+
+    //1回の呼び出しで返せる結果の大きさ（バイト）
+    pub fn with_output(mut self, bytes: usize) -> Self {
+        self.output = bytes;
+        self
+    }
+
+    pub fn fuel(&self) -> u64 {
+        self.fuel
+    }
+
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+
+    pub fn output(&self) -> usize {
+        self.output
+    }
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct WasmCipherPlugin {
+    name: String,
+    engine: Engine,
+    module: Module,
+    limits: PluginLimits,
+}
+
+impl WasmCipherPlugin {
+    pub fn from_bytes(name: &str, bytes: &[u8], limits: PluginLimits) -> Result<Self, PluginError> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|error| PluginError::Engine(error.into()))?;
+        let module = Module::new(&engine, bytes).map_err(|error| PluginError::InvalidModule {
+            plugin: name.to_string(),
+            source: error.into(),
+        })?;
+        if module.imports().len() > 0 {
+            return Err(PluginError::ForbiddenImport(name.to_string()));
+        }
+        for export in ["memory", "alloc", "encode", "decode"] {
+            if module.get_export(export).is_none() {
+                return Err(PluginError::MissingExport {
+                    plugin: name.to_string(),
+                    export,
+                });
+            }
+        }
+        Ok(WasmCipherPlugin {
+            name: name.to_string(),
+            engine,
+            module,
+            limits,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    //呼び出しごとに新しいインスタンスを作るので、前の呼び出しの状態は残らない
+    //戻り値が負のときは None
+    fn call(&self, function: &str, text: &str) -> Result<Option<String>, PluginError> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.memory)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(self.limits.fuel)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory =
+            instance
+                .get_memory(&mut store, "memory")
+                .ok_or(PluginError::MissingExport {
+                    plugin: self.name.clone(),
+                    export: "memory",
+                })?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let convert = instance.get_typed_func::<(i32, i32), i64>(&mut store, function)?;
+
+        let input = text.as_bytes();
+        let len = i32::try_from(input.len()).map_err(|_| PluginError::InputTooLarge)?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory
+            .write(&mut store, ptr as u32 as usize, input)
+            .map_err(|error| PluginError::Runtime(error.into()))?;
+
+        let packed = convert.call(&mut store, (ptr, len))?;
+        if packed < 0 {
+            return Ok(None);
+        }
+        //長さはプラグインが決めるので、確保する前に上限と線形メモリの範囲を確かめる
+        let (ptr, len) = ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize);
+        if len > self.limits.output {
+            return Err(PluginError::OutputTooLarge {
+                len,
+                limit: self.limits.output,
+            });
+        }
+        let size = memory.data_size(&store);
+        let end = ptr.saturating_add(len);
+        if end > size {
+            return Err(PluginError::OutputOutOfBounds { ptr, end, size });
+        }
+        let mut output = vec![0; len];
+        memory
+            .read(&store, ptr, &mut output)
+            .map_err(|error| PluginError::Runtime(error.into()))?;
+        String::from_utf8(output)
+            .map(Some)
+            .map_err(PluginError::InvalidUtf8)
+    }
+
+    fn convert(&self, function: &str, text: &str) -> Result<String, DomainError> {
+        match self.call(function, text) {
+            Ok(Some(converted)) => Ok(converted),
+            Ok(None) => Err(DomainError::PluginRejected {
+                plugin: self.name.clone(),
+            }),
+            Err(error) => Err(DomainError::PluginFailed {
+                plugin: self.name.clone(),
+                source: Box::new(error),
+            }),
+        }
+    }
+}
+
+impl CipherPlugin for WasmCipherPlugin {
+    fn encode(&self, text: &str) -> Result<String, DomainError> {
+        self.convert("encode", text)
+    }
+
+    fn decode(&self, text: &str) -> Result<String, DomainError> {
+        self.convert("decode", text)
+    }
+}
+
+//ディレクトリの *.wasm を、拡張子を除いたファイル名で登録する
+pub fn load_plugins(
+    directory: &Path,
+    registry: &TableRegistry,
+    limits: PluginLimits,
+) -> Result<Vec<String>, PluginError> {
+    let entries = fs::read_dir(directory).map_err(|source| PluginError::Read {
+        path: directory.to_path_buf(),
+        source,
+    })?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "wasm")
+        })
+        .collect();
+    paths.sort();
+
+    let mut names = Vec::new();
+    for path in paths {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| PluginError::InvalidFileName(path.clone()))?
+            .to_string();
+        let bytes = fs::read(&path).map_err(|source| PluginError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let plugin = WasmCipherPlugin::from_bytes(&name, &bytes, limits)?;
+        let description = format!("WebAssembly plugin {}", path.display());
+        registry
+            .register_plugin(&name, &description, Arc::new(plugin))
+            .map_err(|_| PluginError::NameTaken(name.clone()))?;
+        names.push(name);
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::table_registry::TableKind;
+
+    //A-Zを13文字ずらす。encode と decode は同じ処理
+    const ROT13: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) i32.const 1024)
+          (func $rot13 (param $ptr i32) (param $len i32) (result i64)
+            (local $i i32) (local $c i32)
+            (block $done
+              (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                (local.set $c (i32.load8_u (i32.add (local.get $ptr) (local.get $i))))
+                (if (i32.and (i32.ge_u (local.get $c) (i32.const 65))
+                             (i32.le_u (local.get $c) (i32.const 90)))
+                  (then
+                    (i32.store8 (i32.add (local.get $ptr) (local.get $i))
+                      (i32.add (i32.const 65)
+                        (i32.rem_u (i32.add (i32.sub (local.get $c) (i32.const 65))
+                                            (i32.const 13))
+                                   (i32.const 26))))))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)))
+            (i64.or (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                    (i64.extend_i32_u (local.get $len))))
+          (export "encode" (func $rot13))
+          (export "decode" (func $rot13)))
+    "#;
+
+    const SPIN: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) i32.const 0)
+          (func $spin (param i32 i32) (result i64) (loop $forever (br $forever)) i64.const 0)
+          (export "encode" (func $spin))
+          (export "decode" (func $spin)))
+    "#;
+
+    fn plugin(
+        name: &str,
+        wat: &str,
+        limits: PluginLimits,
+    ) -> Result<WasmCipherPlugin, PluginError> {
+        WasmCipherPlugin::from_bytes(name, &wat::parse_str(wat).unwrap(), limits)
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let rot13 = plugin("rot13", ROT13, PluginLimits::new()).unwrap();
+
+        assert_eq!(rot13.encode("HELLO, ワールド").unwrap(), "URYYB, ワールド");
+        assert_eq!(rot13.decode("URYYB").unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn test_fuel_limit() {
+        let spin = plugin("spin", SPIN, PluginLimits::new().with_fuel(1000)).unwrap();
+
+        let error = spin.encode("A").unwrap_err();
+        assert_eq!(error.code(), "plugin_failed");
+        let source = std::error::Error::source(&error).unwrap();
+        assert!(
+            matches!(
+                source.downcast_ref::<PluginError>(),
+                Some(PluginError::Trap(Trap::OutOfFuel))
+            ),
+            "{}",
+            source
+        );
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let greedy = ROT13.replace(
+            "(memory (export \"memory\") 1)",
+            "(memory (export \"memory\") 2)",
+        );
+        let greedy = plugin(
+            "greedy",
+            &greedy,
+            PluginLimits::new().with_memory(64 * 1024),
+        )
+        .unwrap();
+
+        let error = greedy.encode("A").unwrap_err();
+        assert_eq!(error.to_string(), "Plugin greedy failed");
+    }
+
+    #[test]
+    fn test_output_limits() {
+        //線形メモリの外を指す、4 GiB 近い長さを返す
+        let liar = ROT13.replace(
+            "(i64.extend_i32_u (local.get $len))",
+            "(i64.const 0xFFFF_FFF0)",
+        );
+        let liar = plugin(
+            "liar",
+            &liar,
+            PluginLimits::new().with_output(u32::MAX as usize),
+        )
+        .unwrap();
+        let error = liar.call("encode", "A").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Output at 1024..4294968304 is outside linear memory of 65536 bytes"
+        );
+
+        let rot13 = plugin("rot13", ROT13, PluginLimits::new().with_output(4)).unwrap();
+        let error = rot13.call("encode", "HELLO").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Output of 5 bytes exceeds the limit of 4 bytes"
+        );
+        assert_eq!(rot13.encode("HELL").unwrap(), "URYY");
+    }
+
+    #[test]
+    fn test_invalid_plugins() {
+        let imports = r#"(module (import "env" "read" (func)))"#;
+        assert_eq!(
+            plugin("imports", imports, PluginLimits::new())
+                .err()
+                .unwrap()
+                .to_string(),
+            "Plugin imports must not import anything"
+        );
+        assert_eq!(
+            plugin("empty", "(module)", PluginLimits::new())
+                .err()
+                .unwrap()
+                .to_string(),
+            "Plugin empty does not export memory"
+        );
+    }
+
+    #[test]
+    fn test_load_plugins() {
+        let directory = std::env::temp_dir().join(format!("albhed-plugins-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("rot13.wasm"), wat::parse_str(ROT13).unwrap()).unwrap();
+        fs::write(directory.join("README.txt"), "not a plugin").unwrap();
+
+        let registry = TableRegistry::new();
+        let names = load_plugins(&directory, &registry, PluginLimits::new()).unwrap();
+
+        assert_eq!(names, ["rot13"]);
+        assert_eq!(registry.info("rot13").unwrap().kind(), TableKind::Plugin);
+        assert_eq!(registry.plugin("rot13").unwrap().encode("A").unwrap(), "N");
+
+        //組み込みの表と同じ名前のプラグインでは置き換えない
+        fs::remove_file(directory.join("rot13.wasm")).unwrap();
+        fs::write(
+            directory.join("albhed.wasm"),
+            wat::parse_str(ROT13).unwrap(),
+        )
+        .unwrap();
+        let error = load_plugins(&directory, &registry, PluginLimits::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Table albhed is already registered");
+        assert_eq!(registry.info("albhed").unwrap().kind(), TableKind::Builtin);
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -1,9 +1,13 @@
 use std::env;
 
 use actix_cors::Cors;
-use actix_web::{get, post, web, App, HttpResponse, HttpServer};
+use actix_web::http::StatusCode;
+use actix_web::{get, post, web, App, HttpResponse, HttpServer, Resource};
 
+use crate::domain::error::DomainError;
 use crate::interface::adapter::JsonAlBhedTranslatorAdapter;
+use crate::interface::error::AdapterError;
+use crate::usecase::error::UsecaseError;
 
 pub async fn start_server(adapter: JsonAlBhedTranslatorAdapter) -> std::io::Result<()> {
     let port = env::var("BACKEND_PORT")
@@ -12,6 +16,10 @@ pub async fn start_server(adapter: JsonAlBhedTranslatorAdapter) -> std::io::Resu
         .unwrap_or(8080);
     let frontend_origin =
         env::var("FRONTEND_ORIGIN").unwrap_or("http://localhost:5173".to_string());
+    //指定したときだけ、翻訳のエンドポイントで大きな文書も受け付ける（バイト）
+    let max_body_size = env::var("MAX_BODY_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok());
 
     let actix_adapter = web::Data::new(adapter);
     HttpServer::new(move || {
@@ -23,8 +31,12 @@ pub async fn start_server(adapter: JsonAlBhedTranslatorAdapter) -> std::io::Resu
                     .allowed_headers(vec![actix_web::http::header::CONTENT_TYPE]),
             )
             .app_data(actix_adapter.clone())
-            .service(encode_handler)
-            .service(decode_handler)
+            .service(translation_resources(max_body_size))
+            .service(spellcheck_handler)
+            .service(table_handler)
+            .service(name_handler)
+            .service(cipher_list_handler)
+            .service(cipher_alphabet_handler)
             .service(health_check)
     })
     .bind(("0.0.0.0", port))?
@@ -32,32 +44,158 @@ pub async fn start_server(adapter: JsonAlBhedTranslatorAdapter) -> std::io::Resu
     .await
 }
 
-#[post("/encode")]
+//ほかのエンドポイントは actix-web の既定の上限（256KiB）のまま
+fn translation_resources(max_body_size: Option<usize>) -> Vec<Resource> {
+    let routes = [
+        ("/encode", web::post().to(encode_handler)),
+        ("/decode", web::post().to(decode_handler)),
+        ("/translate", web::post().to(translate_handler)),
+    ];
+    routes
+        .into_iter()
+        .map(|(path, route)| {
+            let resource = web::resource(path).route(route);
+            match max_body_size {
+                Some(size) => resource.app_data(web::PayloadConfig::new(size)),
+                None => resource,
+            }
+        })
+        .collect()
+}
+
 async fn encode_handler(
     body: String,
     adapter: web::Data<JsonAlBhedTranslatorAdapter>,
 ) -> HttpResponse {
-    match adapter.encode(&body) {
+    translate_blocking(adapter, body, JsonAlBhedTranslatorAdapter::encode).await
+}
+
+async fn decode_handler(
+    body: String,
+    adapter: web::Data<JsonAlBhedTranslatorAdapter>,
+) -> HttpResponse {
+    translate_blocking(adapter, body, JsonAlBhedTranslatorAdapter::decode).await
+}
+
+async fn translate_handler(
+    body: String,
+    adapter: web::Data<JsonAlBhedTranslatorAdapter>,
+) -> HttpResponse {
+    translate_blocking(adapter, body, JsonAlBhedTranslatorAdapter::translate).await
+}
+
+//大きな文書の変換でワーカーを止めないよう、ブロッキング用のスレッドで変換する
+async fn translate_blocking(
+    adapter: web::Data<JsonAlBhedTranslatorAdapter>,
+    body: String,
+    translate: fn(&JsonAlBhedTranslatorAdapter, &str) -> Result<String, AdapterError>,
+) -> HttpResponse {
+    match web::block(move || translate(&adapter, &body)).await {
+        Ok(Ok(response)) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(response),
+        Ok(Err(error)) => problem(&error),
+        //変換中のパニックやスレッドプールの停止
+        Err(_) => problem_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "worker_failed",
+            "Translation worker stopped before finishing",
+        ),
+    }
+}
+
+#[post("/spellcheck")]
+async fn spellcheck_handler(
+    body: String,
+    adapter: web::Data<JsonAlBhedTranslatorAdapter>,
+) -> HttpResponse {
+    match adapter.spellcheck(&body) {
         Ok(response) => HttpResponse::Ok()
             .content_type("application/json")
             .body(response),
-        Err(error) => HttpResponse::BadRequest().body(error),
+        Err(error) => problem(&error),
     }
 }
 
-#[post("/decode")]
-async fn decode_handler(
+#[post("/tables")]
+async fn table_handler(
     body: String,
     adapter: web::Data<JsonAlBhedTranslatorAdapter>,
 ) -> HttpResponse {
-    match adapter.decode(&body) {
+    match adapter.generate_table(&body) {
+        Ok(response) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(response),
+        Err(error) => problem(&error),
+    }
+}
+
+#[post("/names")]
+async fn name_handler(
+    body: String,
+    adapter: web::Data<JsonAlBhedTranslatorAdapter>,
+) -> HttpResponse {
+    match adapter.generate_names(&body) {
+        Ok(response) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(response),
+        Err(error) => problem(&error),
+    }
+}
+
+#[get("/ciphers")]
+async fn cipher_list_handler(adapter: web::Data<JsonAlBhedTranslatorAdapter>) -> HttpResponse {
+    match adapter.list_ciphers() {
+        Ok(response) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(response),
+        Err(error) => problem(&error),
+    }
+}
+
+#[get("/ciphers/{name}/alphabet")]
+async fn cipher_alphabet_handler(
+    name: web::Path<String>,
+    adapter: web::Data<JsonAlBhedTranslatorAdapter>,
+) -> HttpResponse {
+    match adapter.cipher_alphabet(&name) {
         Ok(response) => HttpResponse::Ok()
             .content_type("application/json")
             .body(response),
-        Err(error) => HttpResponse::BadRequest().body(error),
+        Err(error) => problem(&error),
     }
 }
 
+//RFC 9457 の problem details で返す
+fn problem(error: &AdapterError) -> HttpResponse {
+    let status = match error {
+        AdapterError::InvalidJson(_) => StatusCode::BAD_REQUEST,
+        AdapterError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        AdapterError::Usecase(UsecaseError::UnknownTable(_)) => StatusCode::NOT_FOUND,
+        AdapterError::Usecase(UsecaseError::Domain(DomainError::DuplicateTableName(_))) => {
+            StatusCode::CONFLICT
+        }
+        AdapterError::Usecase(UsecaseError::Domain(DomainError::PluginFailed { .. })) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        AdapterError::Usecase(_) => StatusCode::UNPROCESSABLE_ENTITY,
+    };
+    problem_response(status, error.code(), &error.to_string())
+}
+
+fn problem_response(status: StatusCode, code: &str, detail: &str) -> HttpResponse {
+    let body = serde_json::json!({
+        "type": format!("urn:albhed-translator:problem:{}", code),
+        "title": status.canonical_reason().unwrap_or_default(),
+        "status": status.as_u16(),
+        "detail": detail,
+        "code": code,
+    });
+    HttpResponse::build(status)
+        .content_type("application/problem+json")
+        .body(body.to_string())
+}
+
 #[get("/health")]
 async fn health_check() -> HttpResponse {
     HttpResponse::Ok().finish()
@@ -68,19 +206,32 @@ mod tests {
     use actix_web::test;
 
     use super::*;
-    use crate::usecase::{decode_usecase::DecodeInteractor, encode_usecase::EncodeInteractor};
+    use crate::usecase::{
+        cipher_usecase::CipherInteractor, decode_usecase::DecodeInteractor,
+        encode_usecase::EncodeInteractor, name_usecase::NameInteractor,
+        spellcheck_usecase::SpellcheckInteractor, table_usecase::TableInteractor,
+        translate_usecase::TranslateInteractor,
+    };
+
+    fn create_adapter() -> JsonAlBhedTranslatorAdapter {
+        JsonAlBhedTranslatorAdapter::new(
+            Box::new(EncodeInteractor::new()),
+            Box::new(DecodeInteractor::new()),
+            Box::new(SpellcheckInteractor::new()),
+            Box::new(TableInteractor::new()),
+            Box::new(NameInteractor::new()),
+            Box::new(CipherInteractor::new()),
+            Box::new(TranslateInteractor::new()),
+        )
+    }
 
     #[actix_web::test]
     async fn test_encode_endpoint_valid() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(adapter))
-                .service(encode_handler)
-                .service(decode_handler),
+                .service(translation_resources(None)),
         )
         .await;
 
@@ -98,15 +249,11 @@ mod tests {
 
     #[actix_web::test]
     async fn test_encode_endpoint_invalid() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(adapter))
-                .service(encode_handler)
-                .service(decode_handler),
+                .service(translation_resources(None)),
         )
         .await;
 
@@ -121,15 +268,11 @@ mod tests {
 
     #[actix_web::test]
     async fn test_decode_endpoint_valid() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(adapter))
-                .service(encode_handler)
-                .service(decode_handler),
+                .service(translation_resources(None)),
         )
         .await;
 
@@ -147,15 +290,11 @@ mod tests {
 
     #[actix_web::test]
     async fn test_decode_endpoint_invalid() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(adapter))
-                .service(encode_handler)
-                .service(decode_handler),
+                .service(translation_resources(None)),
         )
         .await;
 
@@ -168,17 +307,256 @@ mod tests {
         assert!(response.status().is_client_error());
     }
 
+    #[actix_web::test]
+    async fn test_spellcheck_endpoint_valid() {
+        let adapter = create_adapter();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(adapter))
+                .service(spellcheck_handler),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/spellcheck")
+            .set_json(serde_json::json!({"text": "FRYD EC DRYD?"}))
+            .to_request();
+
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body, serde_json::json!({"result": []}))
+    }
+
+    #[actix_web::test]
+    async fn test_table_endpoint_valid() {
+        let adapter = create_adapter();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(adapter))
+                .service(table_handler),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/tables")
+            .set_json(serde_json::json!({"passphrase": "zebras", "script": "latin"}))
+            .to_request();
+
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["result"]["mapping"]["Z"], "Y");
+    }
+
+    #[actix_web::test]
+    async fn test_name_endpoint_valid() {
+        let adapter = create_adapter();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(adapter))
+                .service(name_handler),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/names")
+            .set_json(serde_json::json!({"count": 2, "seed": 7}))
+            .to_request();
+
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["result"].as_array().unwrap().len(), 2);
+        assert_eq!(body["seed"], 7);
+    }
+
+    #[actix_web::test]
+    async fn test_cipher_endpoints() {
+        let adapter = create_adapter();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(adapter))
+                .service(cipher_list_handler)
+                .service(cipher_alphabet_handler),
+        )
+        .await;
+
+        let request = test::TestRequest::get().uri("/ciphers").to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["result"][0]["name"], "albhed");
+
+        let request = test::TestRequest::get()
+            .uri("/ciphers/albhed/alphabet")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["result"]["scripts"]["latin"]["forward"]["Z"], "W");
+
+        let request = test::TestRequest::get()
+            .uri("/ciphers/unknown/alphabet")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 404);
+    }
+
+    #[actix_web::test]
+    async fn test_problem_responses() {
+        let adapter = create_adapter();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(adapter))
+                .service(translation_resources(None))
+                .service(cipher_alphabet_handler),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/ciphers/unknown/alphabet")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 404);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/problem+json"
+        );
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "type": "urn:albhed-translator:problem:unknown_table",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "Unknown table: unknown",
+                "code": "unknown_table",
+            })
+        );
+
+        let request = test::TestRequest::post()
+            .uri("/translate")
+            .set_payload(r#"{"text": "", "direction": "encode"}"#)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 422);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "empty_text");
+
+        let request = test::TestRequest::post()
+            .uri("/translate")
+            .set_payload("{")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 400);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "invalid_json");
+    }
+
+    #[actix_web::test]
+    async fn test_problem_conflict() {
+        let error = AdapterError::from(DomainError::DuplicateTableName("game".to_string()));
+        let response = problem(&error);
+        assert_eq!(response.status(), 409);
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "duplicate_table_name");
+    }
+
+    #[actix_web::test]
+    async fn test_problem_when_worker_fails() {
+        fn panic(_: &JsonAlBhedTranslatorAdapter, _: &str) -> Result<String, AdapterError> {
+            panic!("worker failed")
+        }
+
+        let adapter = web::Data::new(create_adapter());
+        let response = translate_blocking(adapter, String::new(), panic).await;
+        assert_eq!(response.status(), 500);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/problem+json"
+        );
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "worker_failed");
+        assert_eq!(body["status"], 500);
+    }
+
+    #[actix_web::test]
+    async fn test_translate_endpoint() {
+        let adapter = create_adapter();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(adapter))
+                .service(translation_resources(None)),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/translate")
+            .set_payload(r#"{"text": "RAMMU", "direction": "decode"}"#)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["result"], "HELLO");
+
+        let request = test::TestRequest::post()
+            .uri("/translate")
+            .set_payload(r#"{"text": "RAMMU"}"#)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), 400);
+    }
+
+    #[actix_web::test]
+    async fn test_body_size_limits() {
+        let payload = serde_json::json!({"text": "A".repeat(300 * 1024)}).to_string();
+        let request = |uri: &str| {
+            test::TestRequest::post()
+                .uri(uri)
+                .set_payload(payload.clone())
+                .to_request()
+        };
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(create_adapter()))
+                .service(translation_resources(None)),
+        )
+        .await;
+        let response = test::call_service(&app, request("/encode")).await;
+        assert_eq!(response.status(), 413);
+
+        //MAX_BODY_SIZE は翻訳のエンドポイントにだけ効く
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(create_adapter()))
+                .service(translation_resources(Some(1024 * 1024)))
+                .service(spellcheck_handler),
+        )
+        .await;
+        let response = test::call_service(&app, request("/encode")).await;
+        assert!(response.status().is_success());
+        let response = test::call_service(&app, request("/spellcheck")).await;
+        assert_eq!(response.status(), 413);
+    }
+
     #[actix_web::test]
     async fn test_health_check() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(adapter))
-                .service(encode_handler)
-                .service(decode_handler)
+                .service(translation_resources(None))
                 .service(health_check),
         )
         .await;
@@ -1,66 +1,991 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+use crate::domain::entities::{Language, Script};
+use crate::domain::error::DomainError;
+use crate::domain::lint::{LintFinding, LintKind};
+use crate::domain::markup::TagHandling;
+use crate::domain::name_generator::GeneratedName;
+use crate::domain::pipeline::{processor, Phase, Pipeline, StageReport, TextChange};
+use crate::domain::pronunciation::WordPronunciation;
+use crate::domain::protection::{EntityKind, ProtectedSpan};
+use crate::domain::ssml::SsmlOptions;
+use crate::domain::table_registry::{TableInfo, TableKind};
+use crate::interface::error::AdapterError;
 use crate::usecase::{
+    cipher_usecase::{CipherAlphabetInputData, CipherInputPort},
     decode_usecase::{DecodeInputData, DecodeInputPort},
     encode_usecase::{EncodeInputData, EncodeInputPort},
+    input_lint::LintMode,
+    name_usecase::{NameInputData, NameInputPort},
+    spellcheck_usecase::{SpellcheckInputData, SpellcheckInputPort},
+    table_usecase::{TableInputData, TableInputPort},
+    translate_usecase::{
+        CaseMode, Direction, TranslateInputData, TranslateInputPort, TranslationOptions,
+        TranslationWarning,
+    },
 };
 
 #[derive(Deserialize)]
 pub struct AlBhedTransferRequest {
     text: String,
+    #[serde(default)]
+    table: Option<String>,
+    #[serde(default)]
+    languages: Option<Vec<LanguageRequest>>,
+    #[serde(default)]
+    protect: Option<Vec<EntityKindValue>>,
+    #[serde(default)]
+    markup: Option<MarkupRequest>,
+    #[serde(default)]
+    lint: Option<LintModeRequest>,
+    #[serde(default)]
+    pronounce: bool,
+    #[serde(default)]
+    ssml: Option<SsmlRequest>,
+    #[serde(default)]
+    pipeline: Option<PipelineRequest>,
+}
+
+#[derive(Deserialize)]
+pub struct TranslateRequest {
+    text: String,
+    direction: DirectionValue,
+    #[serde(default)]
+    table: Option<String>,
+    #[serde(default)]
+    case: Option<CaseRequest>,
+    #[serde(default)]
+    scripts: Option<Vec<ScriptValue>>,
+    #[serde(default)]
+    protect: Option<Vec<EntityKindValue>>,
+    #[serde(default)]
+    markup: Option<MarkupRequest>,
+    #[serde(default)]
+    lint: Option<LintModeRequest>,
+    //覚えた文字を並べた文字列
+    #[serde(default)]
+    primers: Option<String>,
+    #[serde(default)]
+    pronounce: bool,
+    #[serde(default)]
+    ssml: Option<SsmlRequest>,
+    #[serde(default)]
+    pipeline: Option<PipelineRequest>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DirectionValue {
+    Encode,
+    Decode,
+}
+
+impl From<DirectionValue> for Direction {
+    fn from(direction: DirectionValue) -> Self {
+        match direction {
+            DirectionValue::Encode => Direction::Encode,
+            DirectionValue::Decode => Direction::Decode,
+        }
+    }
+}
+
+impl From<Direction> for DirectionValue {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Encode => DirectionValue::Encode,
+            Direction::Decode => DirectionValue::Decode,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseRequest {
+    Exact,
+    Preserve,
+}
+
+impl From<CaseRequest> for CaseMode {
+    fn from(case: CaseRequest) -> Self {
+        match case {
+            CaseRequest::Exact => CaseMode::Exact,
+            CaseRequest::Preserve => CaseMode::Preserve,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SsmlRequest {
+    #[serde(default = "default_ssml_language")]
+    language: String,
+    #[serde(default)]
+    hints: bool,
+}
+
+fn default_ssml_language() -> String {
+    "en-US".to_string()
+}
+
+//profile の処理のあとに pre / post の処理を足す
+#[derive(Deserialize)]
+pub struct PipelineRequest {
+    #[serde(default)]
+    profile: Option<String>,
+    #[serde(default)]
+    pre: Vec<String>,
+    #[serde(default)]
+    post: Vec<String>,
+}
+
+impl TryFrom<&PipelineRequest> for Pipeline {
+    type Error = DomainError;
+
+    fn try_from(request: &PipelineRequest) -> Result<Self, Self::Error> {
+        let mut pipeline = match &request.profile {
+            Some(profile) => Pipeline::profile(profile)?,
+            None => Pipeline::new(),
+        };
+        for name in &request.pre {
+            pipeline = pipeline.with_pre(processor(name)?);
+        }
+        for name in &request.post {
+            pipeline = pipeline.with_post(processor(name)?);
+        }
+        Ok(pipeline)
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkupRequest {
+    Strip,
+    Keep,
+}
+
+impl From<MarkupRequest> for TagHandling {
+    fn from(markup: MarkupRequest) -> Self {
+        match markup {
+            MarkupRequest::Strip => TagHandling::Strip,
+            MarkupRequest::Keep => TagHandling::Keep,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LintModeRequest {
+    Off,
+    Report,
+    Normalize,
+    Reject,
+}
+
+impl From<LintModeRequest> for LintMode {
+    fn from(mode: LintModeRequest) -> Self {
+        match mode {
+            LintModeRequest::Off => LintMode::Off,
+            LintModeRequest::Report => LintMode::Report,
+            LintModeRequest::Normalize => LintMode::Normalize,
+            LintModeRequest::Reject => LintMode::Reject,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageRequest {
+    English,
+    Japanese,
+}
+
+impl From<LanguageRequest> for Language {
+    fn from(language: LanguageRequest) -> Self {
+        match language {
+            LanguageRequest::English => Language::English,
+            LanguageRequest::Japanese => Language::Japanese,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKindValue {
+    Code,
+    Url,
+    Email,
+    Placeholder,
+    Mention,
+    Hashtag,
+}
+
+impl From<EntityKindValue> for EntityKind {
+    fn from(kind: EntityKindValue) -> Self {
+        match kind {
+            EntityKindValue::Code => EntityKind::Code,
+            EntityKindValue::Url => EntityKind::Url,
+            EntityKindValue::Email => EntityKind::Email,
+            EntityKindValue::Placeholder => EntityKind::Placeholder,
+            EntityKindValue::Mention => EntityKind::Mention,
+            EntityKindValue::Hashtag => EntityKind::Hashtag,
+        }
+    }
+}
+
+impl From<EntityKind> for EntityKindValue {
+    fn from(kind: EntityKind) -> Self {
+        match kind {
+            EntityKind::Code => EntityKindValue::Code,
+            EntityKind::Url => EntityKindValue::Url,
+            EntityKind::Email => EntityKindValue::Email,
+            EntityKind::Placeholder => EntityKindValue::Placeholder,
+            EntityKind::Mention => EntityKindValue::Mention,
+            EntityKind::Hashtag => EntityKindValue::Hashtag,
+        }
+    }
+}
+
+//スペルチェックは表や変換の指定を使わないので、知らないフィールドは誤りにする
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpellcheckRequest {
+    text: String,
+}
+
+#[derive(Deserialize)]
+pub struct TableGenerateRequest {
+    passphrase: String,
+    #[serde(default)]
+    script: ScriptRequest,
+    #[serde(default = "default_allow_self_mapping")]
+    allow_self_mapping: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptRequest {
+    Latin,
+    Kana,
+    #[default]
+    Both,
+}
+
+fn default_allow_self_mapping() -> bool {
+    false
+}
+
+#[derive(Deserialize)]
+pub struct NameGenerateRequest {
+    #[serde(default = "default_name_count")]
+    count: usize,
+    #[serde(default)]
+    syllables: Option<usize>,
+    #[serde(default)]
+    neutral: bool,
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+fn default_name_count() -> usize {
+    1
 }
 
 #[derive(Serialize)]
 pub struct AlBhedTransferResponse {
     result: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protected: Option<Vec<ProtectedSpanResponse>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    lint: Vec<LintFindingResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pronunciation: Option<Vec<PronunciationResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssml: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stages: Vec<StageReportResponse>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhaseValue {
+    Pre,
+    Post,
+}
+
+impl From<Phase> for PhaseValue {
+    fn from(phase: Phase) -> Self {
+        match phase {
+            Phase::Pre => PhaseValue::Pre,
+            Phase::Post => PhaseValue::Post,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StageReportResponse {
+    stage: String,
+    phase: PhaseValue,
+    changes: Vec<TextChangeResponse>,
+}
+
+impl From<&StageReport> for StageReportResponse {
+    fn from(report: &StageReport) -> Self {
+        StageReportResponse {
+            stage: report.stage().to_string(),
+            phase: report.phase().into(),
+            changes: report
+                .changes()
+                .iter()
+                .map(TextChangeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TextChangeResponse {
+    start: usize,
+    end: usize,
+    before: String,
+    after: String,
+}
+
+impl From<&TextChange> for TextChangeResponse {
+    fn from(change: &TextChange) -> Self {
+        TextChangeResponse {
+            start: change.start(),
+            end: change.end(),
+            before: change.before().to_string(),
+            after: change.after().to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TranslateResponse {
+    result: String,
+    direction: DirectionValue,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<WarningResponse>,
+    metadata: MetadataResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protected: Option<Vec<ProtectedSpanResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pronunciation: Option<Vec<PronunciationResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssml: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WarningResponse {
+    SuspiciousCharacter { finding: LintFindingResponse },
+    IgnoredOption { option: &'static str },
+}
+
+impl From<&TranslationWarning> for WarningResponse {
+    fn from(warning: &TranslationWarning) -> Self {
+        match warning {
+            TranslationWarning::SuspiciousCharacter(finding) => {
+                WarningResponse::SuspiciousCharacter {
+                    finding: finding.into(),
+                }
+            }
+            TranslationWarning::IgnoredOption(option) => WarningResponse::IgnoredOption { option },
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MetadataResponse {
+    table: String,
+    kind: TableKindValue,
+    scripts: Vec<ScriptValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stages: Vec<StageReportResponse>,
+}
+
+#[derive(Serialize)]
+pub struct PronunciationResponse {
+    start: usize,
+    end: usize,
+    word: String,
+    syllables: Vec<String>,
+    respelling: String,
+    ipa: String,
+}
+
+impl From<&WordPronunciation> for PronunciationResponse {
+    fn from(word: &WordPronunciation) -> Self {
+        PronunciationResponse {
+            start: word.start(),
+            end: word.end(),
+            word: word.word().to_string(),
+            syllables: word.syllables().to_vec(),
+            respelling: word.respelling().to_string(),
+            ipa: word.ipa().to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProtectedSpanResponse {
+    kind: EntityKindValue,
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl From<&ProtectedSpan> for ProtectedSpanResponse {
+    fn from(span: &ProtectedSpan) -> Self {
+        ProtectedSpanResponse {
+            kind: span.kind().into(),
+            start: span.start(),
+            end: span.end(),
+            text: span.text().to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKindValue {
+    BidiControl,
+    ZeroWidth,
+    Homoglyph,
+}
+
+impl From<LintKind> for LintKindValue {
+    fn from(kind: LintKind) -> Self {
+        match kind {
+            LintKind::BidiControl => LintKindValue::BidiControl,
+            LintKind::ZeroWidth => LintKindValue::ZeroWidth,
+            LintKind::Homoglyph => LintKindValue::Homoglyph,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LintFindingResponse {
+    kind: LintKindValue,
+    position: usize,
+    //制御文字は見えないので U+XXXX で返す
+    code_point: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replacement: Option<char>,
+}
+
+impl From<&LintFinding> for LintFindingResponse {
+    fn from(finding: &LintFinding) -> Self {
+        LintFindingResponse {
+            kind: finding.kind().into(),
+            position: finding.position(),
+            code_point: format!("U+{:04X}", finding.character() as u32),
+            replacement: finding.replacement(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SpellcheckResponse {
+    result: Vec<MisspellingResponse>,
+}
+
+#[derive(Serialize)]
+pub struct MisspellingResponse {
+    start: usize,
+    end: usize,
+    text: String,
+    decoded: String,
+    suggestions: Vec<SuggestionResponse>,
+}
+
+#[derive(Serialize)]
+pub struct SuggestionResponse {
+    text: String,
+    decoded: String,
+    distance: usize,
+}
+
+#[derive(Serialize)]
+pub struct NameGenerateResponse {
+    result: Vec<NameResponse>,
+    seed: u64,
+}
+
+#[derive(Serialize)]
+pub struct NameResponse {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meaning: Option<String>,
+}
+
+impl From<&GeneratedName> for NameResponse {
+    fn from(name: &GeneratedName) -> Self {
+        NameResponse {
+            name: name.name().to_string(),
+            meaning: name.meaning().map(str::to_string),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CipherListResponse {
+    result: Vec<CipherResponse>,
+}
+
+#[derive(Serialize)]
+pub struct CipherResponse {
+    name: String,
+    kind: TableKindValue,
+    description: String,
+    scripts: Vec<ScriptValue>,
+    size: usize,
+}
+
+impl From<&TableInfo> for CipherResponse {
+    fn from(info: &TableInfo) -> Self {
+        CipherResponse {
+            name: info.name().to_string(),
+            kind: info.kind().into(),
+            description: info.description().to_string(),
+            scripts: info.languages().iter().map(|&l| l.into()).collect(),
+            size: info.size(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableKindValue {
+    Builtin,
+    Keyed,
+    Plugin,
+    Custom,
+}
+
+impl From<TableKind> for TableKindValue {
+    fn from(kind: TableKind) -> Self {
+        match kind {
+            TableKind::Builtin => TableKindValue::Builtin,
+            TableKind::Keyed => TableKindValue::Keyed,
+            TableKind::Plugin => TableKindValue::Plugin,
+            TableKind::Custom => TableKindValue::Custom,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptValue {
+    Latin,
+    Kana,
+}
+
+impl From<Script> for ScriptValue {
+    fn from(script: Script) -> Self {
+        match script {
+            Script::Latin => ScriptValue::Latin,
+            Script::Kana => ScriptValue::Kana,
+        }
+    }
+}
+
+impl From<ScriptValue> for Script {
+    fn from(script: ScriptValue) -> Self {
+        match script {
+            ScriptValue::Latin => Script::Latin,
+            ScriptValue::Kana => Script::Kana,
+        }
+    }
+}
+
+impl From<Language> for ScriptValue {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::English => ScriptValue::Latin,
+            Language::Japanese => ScriptValue::Kana,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CipherAlphabetResponse {
+    result: AlphabetResponse,
+}
+
+#[derive(Serialize)]
+pub struct AlphabetResponse {
+    name: String,
+    scripts: BTreeMap<ScriptValue, ScriptAlphabetResponse>,
+}
+
+#[derive(Serialize)]
+pub struct ScriptAlphabetResponse {
+    //平文 -> アルベド語
+    forward: BTreeMap<char, char>,
+    //アルベド語 -> 平文
+    reverse: BTreeMap<char, char>,
+}
+
+#[derive(Serialize)]
+pub struct TableGenerateResponse {
+    result: TableResponse,
+}
+
+#[derive(Serialize)]
+pub struct TableResponse {
+    hash: String,
+    mapping: BTreeMap<char, char>,
 }
 
 pub struct JsonAlBhedTranslatorAdapter {
     encode_input_port: Box<dyn EncodeInputPort + Sync + Send>,
     decode_input_port: Box<dyn DecodeInputPort + Sync + Send>,
+    spellcheck_input_port: Box<dyn SpellcheckInputPort + Sync + Send>,
+    table_input_port: Box<dyn TableInputPort + Sync + Send>,
+    name_input_port: Box<dyn NameInputPort + Sync + Send>,
+    cipher_input_port: Box<dyn CipherInputPort + Sync + Send>,
+    translate_input_port: Box<dyn TranslateInputPort + Sync + Send>,
 }
 
 impl JsonAlBhedTranslatorAdapter {
     pub fn new(
         encode_port: Box<dyn EncodeInputPort + Sync + Send>,
         decode_port: Box<dyn DecodeInputPort + Sync + Send>,
+        spellcheck_port: Box<dyn SpellcheckInputPort + Sync + Send>,
+        table_port: Box<dyn TableInputPort + Sync + Send>,
+        name_port: Box<dyn NameInputPort + Sync + Send>,
+        cipher_port: Box<dyn CipherInputPort + Sync + Send>,
+        translate_port: Box<dyn TranslateInputPort + Sync + Send>,
     ) -> Self {
         JsonAlBhedTranslatorAdapter {
             encode_input_port: encode_port,
             decode_input_port: decode_port,
+            spellcheck_input_port: spellcheck_port,
+            table_input_port: table_port,
+            name_input_port: name_port,
+            cipher_input_port: cipher_port,
+            translate_input_port: translate_port,
+        }
+    }
+
+    pub fn translate(&self, json: &str) -> Result<String, AdapterError> {
+        let request: TranslateRequest =
+            serde_json::from_str(json).map_err(AdapterError::InvalidJson)?;
+        let mut options = TranslationOptions::builder();
+        if let Some(table) = &request.table {
+            options = options.with_table(table);
+        }
+        if let Some(case) = request.case {
+            options = options.with_case(case.into());
+        }
+        if let Some(scripts) = &request.scripts {
+            let scripts: Vec<Script> = scripts.iter().map(|&s| s.into()).collect();
+            options = options.with_scripts(&scripts);
+        }
+        if let Some(protect) = &request.protect {
+            let protections: Vec<EntityKind> = protect.iter().map(|&k| k.into()).collect();
+            options = options.with_protections(&protections);
+        }
+        if let Some(markup) = request.markup {
+            options = options.with_markup(markup.into());
+        }
+        if let Some(lint) = request.lint {
+            options = options.with_lint_mode(lint.into());
+        }
+        if let Some(primers) = &request.primers {
+            let letters: Vec<char> = primers.chars().collect();
+            options = options.with_primers(&letters);
         }
+        if request.pronounce {
+            options = options.with_pronunciation();
+        }
+        if let Some(ssml) = &request.ssml {
+            let mut ssml_options = SsmlOptions::new(&ssml.language);
+            if ssml.hints {
+                ssml_options = ssml_options.with_hints();
+            }
+            options = options.with_ssml(ssml_options);
+        }
+        if let Some(pipeline) = &request.pipeline {
+            options = options.with_pipeline(pipeline.try_into()?);
+        }
+        let translate_input_data =
+            TranslateInputData::new(&request.text, request.direction.into(), options.build());
+        let translated = self.translate_input_port.translate(translate_input_data)?;
+        let metadata = translated.get_metadata();
+        let response = TranslateResponse {
+            result: translated.get_text().to_string(),
+            direction: translated.get_direction().into(),
+            warnings: translated
+                .get_warnings()
+                .iter()
+                .map(WarningResponse::from)
+                .collect(),
+            metadata: MetadataResponse {
+                table: metadata.get_table().to_string(),
+                kind: metadata.get_kind().into(),
+                scripts: metadata.get_scripts().iter().map(|&s| s.into()).collect(),
+                stages: metadata
+                    .get_stage_reports()
+                    .iter()
+                    .map(StageReportResponse::from)
+                    .collect(),
+            },
+            protected: translated
+                .get_protected()
+                .map(|spans| spans.iter().map(ProtectedSpanResponse::from).collect()),
+            pronunciation: translated
+                .get_pronunciation()
+                .map(|words| words.iter().map(PronunciationResponse::from).collect()),
+            ssml: translated.get_ssml().map(str::to_string),
+        };
+        serde_json::to_string(&response).map_err(AdapterError::Serialization)
     }
 
-    pub fn encode(&self, json: &str) -> Result<String, String> {
+    pub fn encode(&self, json: &str) -> Result<String, AdapterError> {
         let request: AlBhedTransferRequest =
-            serde_json::from_str(json).map_err(|_| "Invalid JSON format".to_string())?;
-        let encode_input_data = EncodeInputData::new(&request.text);
+            serde_json::from_str(json).map_err(AdapterError::InvalidJson)?;
+        let mut encode_input_data = EncodeInputData::new(&request.text);
+        if let Some(table) = &request.table {
+            encode_input_data = encode_input_data.with_table(table);
+        }
+        if let Some(languages) = &request.languages {
+            let languages: Vec<Language> = languages.iter().map(|&l| l.into()).collect();
+            encode_input_data = encode_input_data.with_languages(&languages);
+        }
+        if let Some(protect) = &request.protect {
+            let protections: Vec<EntityKind> = protect.iter().map(|&k| k.into()).collect();
+            encode_input_data = encode_input_data.with_protections(&protections);
+        }
+        if let Some(markup) = request.markup {
+            encode_input_data = encode_input_data.with_markup(markup.into());
+        }
+        if let Some(lint) = request.lint {
+            encode_input_data = encode_input_data.with_lint_mode(lint.into());
+        }
+        if let Some(pipeline) = &request.pipeline {
+            encode_input_data = encode_input_data.with_pipeline(pipeline.try_into()?);
+        }
+        if request.pronounce {
+            encode_input_data = encode_input_data.with_pronunciation();
+        }
+        if let Some(ssml) = &request.ssml {
+            let mut options = SsmlOptions::new(&ssml.language);
+            if ssml.hints {
+                options = options.with_hints();
+            }
+            encode_input_data = encode_input_data.with_ssml(options);
+        }
         match self.encode_input_port.encode(encode_input_data) {
             Ok(encoded) => {
                 let response = AlBhedTransferResponse {
                     result: encoded.get_text().to_string(),
+                    protected: encoded
+                        .get_protected()
+                        .map(|spans| spans.iter().map(ProtectedSpanResponse::from).collect()),
+                    lint: encoded
+                        .get_lint_findings()
+                        .iter()
+                        .map(LintFindingResponse::from)
+                        .collect(),
+                    pronunciation: encoded
+                        .get_pronunciation()
+                        .map(|words| words.iter().map(PronunciationResponse::from).collect()),
+                    ssml: encoded.get_ssml().map(str::to_string),
+                    stages: encoded
+                        .get_stage_reports()
+                        .iter()
+                        .map(StageReportResponse::from)
+                        .collect(),
                 };
-                serde_json::to_string(&response)
-                    .map_err(|_| "Failed to serialize response".to_string())
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
             }
-            Err(error) => Err(error),
+            Err(error) => Err(error.into()),
         }
     }
 
-    pub fn decode(&self, json: &str) -> Result<String, String> {
+    pub fn decode(&self, json: &str) -> Result<String, AdapterError> {
         let request: AlBhedTransferRequest =
-            serde_json::from_str(json).map_err(|_| "Invalid JSON format".to_string())?;
-        let decode_input_data = DecodeInputData::new(&request.text);
+            serde_json::from_str(json).map_err(AdapterError::InvalidJson)?;
+        let mut decode_input_data = DecodeInputData::new(&request.text);
+        if let Some(table) = &request.table {
+            decode_input_data = decode_input_data.with_table(table);
+        }
+        if let Some(languages) = &request.languages {
+            let languages: Vec<Language> = languages.iter().map(|&l| l.into()).collect();
+            decode_input_data = decode_input_data.with_languages(&languages);
+        }
+        if let Some(protect) = &request.protect {
+            let protections: Vec<EntityKind> = protect.iter().map(|&k| k.into()).collect();
+            decode_input_data = decode_input_data.with_protections(&protections);
+        }
+        if let Some(markup) = request.markup {
+            decode_input_data = decode_input_data.with_markup(markup.into());
+        }
+        if let Some(lint) = request.lint {
+            decode_input_data = decode_input_data.with_lint_mode(lint.into());
+        }
+        if let Some(pipeline) = &request.pipeline {
+            decode_input_data = decode_input_data.with_pipeline(pipeline.try_into()?);
+        }
         match self.decode_input_port.decode(decode_input_data) {
             Ok(decoded) => {
                 let response = AlBhedTransferResponse {
                     result: decoded.get_text().to_string(),
+                    protected: decoded
+                        .get_protected()
+                        .map(|spans| spans.iter().map(ProtectedSpanResponse::from).collect()),
+                    lint: decoded
+                        .get_lint_findings()
+                        .iter()
+                        .map(LintFindingResponse::from)
+                        .collect(),
+                    pronunciation: None,
+                    ssml: None,
+                    stages: decoded
+                        .get_stage_reports()
+                        .iter()
+                        .map(StageReportResponse::from)
+                        .collect(),
+                };
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn spellcheck(&self, json: &str) -> Result<String, AdapterError> {
+        let request: SpellcheckRequest =
+            serde_json::from_str(json).map_err(AdapterError::InvalidJson)?;
+        let spellcheck_input_data = SpellcheckInputData::new(&request.text);
+        match self.spellcheck_input_port.spellcheck(spellcheck_input_data) {
+            Ok(checked) => {
+                let response = SpellcheckResponse {
+                    result: checked
+                        .get_misspellings()
+                        .iter()
+                        .map(|misspelling| MisspellingResponse {
+                            start: misspelling.start(),
+                            end: misspelling.end(),
+                            text: misspelling.text().to_string(),
+                            decoded: misspelling.decoded().to_string(),
+                            suggestions: misspelling
+                                .suggestions()
+                                .iter()
+                                .map(|suggestion| SuggestionResponse {
+                                    text: suggestion.text().to_string(),
+                                    decoded: suggestion.decoded().to_string(),
+                                    distance: suggestion.distance(),
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                };
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn generate_table(&self, json: &str) -> Result<String, AdapterError> {
+        let request: TableGenerateRequest =
+            serde_json::from_str(json).map_err(AdapterError::InvalidJson)?;
+        let scripts = match request.script {
+            ScriptRequest::Latin => vec![Script::Latin],
+            ScriptRequest::Kana => vec![Script::Kana],
+            ScriptRequest::Both => vec![Script::Latin, Script::Kana],
+        };
+        let table_input_data =
+            TableInputData::new(&request.passphrase, &scripts, request.allow_self_mapping);
+        match self.table_input_port.generate(table_input_data) {
+            Ok(generated) => {
+                let response = TableGenerateResponse {
+                    result: TableResponse {
+                        hash: generated.get_hash().to_string(),
+                        mapping: generated.get_pairs().iter().copied().collect(),
+                    },
+                };
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn generate_names(&self, json: &str) -> Result<String, AdapterError> {
+        let request: NameGenerateRequest =
+            serde_json::from_str(json).map_err(AdapterError::InvalidJson)?;
+        let mut name_input_data = NameInputData::new(request.count);
+        if let Some(syllables) = request.syllables {
+            name_input_data = name_input_data.with_syllables(syllables);
+        }
+        if request.neutral {
+            name_input_data = name_input_data.with_neutral_style();
+        }
+        if let Some(seed) = request.seed {
+            name_input_data = name_input_data.with_seed(seed);
+        }
+        match self.name_input_port.generate(name_input_data) {
+            Ok(generated) => {
+                let response = NameGenerateResponse {
+                    result: generated
+                        .get_names()
+                        .iter()
+                        .map(NameResponse::from)
+                        .collect(),
+                    seed: generated.get_seed(),
+                };
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn list_ciphers(&self) -> Result<String, AdapterError> {
+        match self.cipher_input_port.list() {
+            Ok(listed) => {
+                let response = CipherListResponse {
+                    result: listed
+                        .get_tables()
+                        .iter()
+                        .map(CipherResponse::from)
+                        .collect(),
+                };
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn cipher_alphabet(&self, name: &str) -> Result<String, AdapterError> {
+        match self
+            .cipher_input_port
+            .alphabet(CipherAlphabetInputData::new(name))
+        {
+            Ok(alphabet) => {
+                let scripts = alphabet
+                    .get_alphabets()
+                    .iter()
+                    .map(|(script, pairs)| {
+                        let alphabet = ScriptAlphabetResponse {
+                            forward: pairs.iter().copied().collect(),
+                            reverse: pairs.iter().map(|&(k, v)| (v, k)).collect(),
+                        };
+                        (ScriptValue::from(*script), alphabet)
+                    })
+                    .collect();
+                let response = CipherAlphabetResponse {
+                    result: AlphabetResponse {
+                        name: alphabet.get_name().to_string(),
+                        scripts,
+                    },
                 };
-                serde_json::to_string(&response)
-                    .map_err(|_| "Failed to serialize response".to_string())
+                serde_json::to_string(&response).map_err(AdapterError::Serialization)
             }
-            Err(error) => Err(error),
+            Err(error) => Err(error.into()),
         }
     }
 }
@@ -68,14 +993,31 @@ impl JsonAlBhedTranslatorAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::usecase::{decode_usecase::DecodeInteractor, encode_usecase::EncodeInteractor};
+    use crate::domain::table_registry::TableRegistry;
+    use crate::usecase::{
+        cipher_usecase::CipherInteractor, decode_usecase::DecodeInteractor,
+        encode_usecase::EncodeInteractor, name_usecase::NameInteractor,
+        spellcheck_usecase::SpellcheckInteractor, table_usecase::TableInteractor,
+        translate_usecase::TranslateInteractor,
+    };
+    use std::sync::Arc;
+
+    fn create_adapter() -> JsonAlBhedTranslatorAdapter {
+        let registry = Arc::new(TableRegistry::new());
+        JsonAlBhedTranslatorAdapter::new(
+            Box::new(EncodeInteractor::with_registry(registry.clone())),
+            Box::new(DecodeInteractor::with_registry(registry.clone())),
+            Box::new(SpellcheckInteractor::new()),
+            Box::new(TableInteractor::with_registry(registry.clone())),
+            Box::new(NameInteractor::new()),
+            Box::new(CipherInteractor::with_registry(registry.clone())),
+            Box::new(TranslateInteractor::with_registry(registry)),
+        )
+    }
 
     #[test]
     fn test_encode_valid_json() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let json = r#"{"text": "がんばろう！"}"#;
         let result = adapter.encode(json).unwrap();
         assert_eq!(result, r#"{"result":"ダンザノフ！"}"#);
@@ -83,10 +1025,7 @@ mod tests {
 
     #[test]
     fn test_encode_invalid_json() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let json = r#"invalid json"#;
         let result = adapter.encode(json);
         assert!(result.is_err());
@@ -94,10 +1033,7 @@ mod tests {
 
     #[test]
     fn test_decode_valid_json() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let json = r#"{"text": "ヤヌサー"}"#;
         let result = adapter.decode(json).unwrap();
         assert_eq!(result, r#"{"result":"ますたー"}"#);
@@ -105,12 +1041,283 @@ mod tests {
 
     #[test]
     fn test_decode_invalid_json() {
-        let encode_port = EncodeInteractor::new();
-        let decode_port = DecodeInteractor::new();
-        let adapter =
-            JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+        let adapter = create_adapter();
         let json = r#"invalid json"#;
         let result = adapter.decode(json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_spellcheck_valid_json() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "FRYD EC DRYT?"}"#;
+        let result = adapter.spellcheck(json).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(body["result"][0]["start"], 8);
+        assert_eq!(body["result"][0]["end"], 12);
+        assert_eq!(body["result"][0]["suggestions"][0]["text"], "DRYD");
+        assert_eq!(body["result"][0]["suggestions"][0]["decoded"], "THAT");
+    }
+
+    #[test]
+    fn test_spellcheck_rejects_unknown_fields() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "FRYD EC DRYT?", "table": "albhed"}"#;
+        let error = adapter.spellcheck(json).unwrap_err();
+        assert_eq!(error.code(), "invalid_json");
+    }
+
+    #[test]
+    fn test_encode_with_languages() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "HELLO こんにちは", "languages": ["japanese"]}"#;
+        let result = adapter.encode(json).unwrap();
+        assert_eq!(result, r#"{"result":"HELLO ヨンシヒマ"}"#);
+    }
+
+    #[test]
+    fn test_encode_with_protect() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "HI @RIKKU", "protect": ["mention", "url"]}"#;
+        let result = adapter.encode(json).unwrap();
+        assert_eq!(
+            result,
+            r#"{"result":"RE @RIKKU","protected":[{"kind":"mention","start":3,"end":9,"text":"@RIKKU"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_encode_with_placeholders() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "HI {name}, {n, plural, one {# DAY} other {# DAYS}}", "protect": ["placeholder"]}"#;
+        let result = adapter.encode(json).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(
+            body["result"],
+            "RE {name}, {n, plural, one {# TYO} other {# TYOC}}"
+        );
+    }
+
+    #[test]
+    fn test_encode_with_markup() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "HI <albhed>HI</albhed>", "markup": "keep"}"#;
+        let result = adapter.encode(json).unwrap();
+        assert_eq!(result, r#"{"result":"HI <albhed>RE</albhed>"}"#);
+
+        let json = r#"{"text": "HI <albhed>HI", "markup": "strip"}"#;
+        assert_eq!(
+            adapter.encode(json).err().unwrap().to_string(),
+            "Unclosed tag <albhed> at 3"
+        );
+    }
+
+    #[test]
+    fn test_encode_with_lint() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "\u0410L\u200BBHED", "lint": "normalize"}"#;
+        let result = adapter.encode(json).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(body["result"], "YM\u{200B}PRAT");
+        assert_eq!(
+            body["lint"].to_string(),
+            r#"[{"code_point":"U+0410","kind":"homoglyph","position":0,"replacement":"A"},{"code_point":"U+200B","kind":"zero_width","position":2}]"#
+        );
+
+        let json = r#"{"text": "AB\u202EC", "lint": "reject"}"#;
+        assert_eq!(
+            adapter.encode(json).err().unwrap().to_string(),
+            "Suspicious character U+202E at 2"
+        );
+    }
+
+    #[test]
+    fn test_encode_with_pronunciation() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "ALBHED", "pronounce": true}"#;
+        let result = adapter.encode(json).unwrap();
+        assert_eq!(
+            result,
+            r#"{"result":"YMPRAT","pronunciation":[{"start":0,"end":6,"word":"YMPRAT","syllables":["ihm","praht"],"respelling":"IHM-praht","ipa":"ˈɪm.pɹat"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_encode_with_ssml() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "HI!", "ssml": {"hints": true}}"#;
+        let result = adapter.encode(json).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(
+            body["ssml"],
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><speak version=\"1.1\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"art-x-albhed\"><prosody volume=\"loud\"><phoneme alphabet=\"ipa\" ph=\"ɹe\">RE</phoneme>!</prosody><break strength=\"strong\"/></speak>"
+        );
+    }
+
+    #[test]
+    fn test_generate_table_and_encode() {
+        let adapter = create_adapter();
+        let json = r#"{"passphrase": "zebras", "script": "latin", "allow_self_mapping": true}"#;
+        let result = adapter.generate_table(json).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(body["result"]["mapping"]["A"], "Z");
+
+        let hash = body["result"]["hash"].as_str().unwrap();
+        let json = serde_json::json!({"text": "ABCDEZ", "table": hash}).to_string();
+        assert_eq!(adapter.encode(&json).unwrap(), r#"{"result":"ZEBRAY"}"#);
+        let json = serde_json::json!({"text": "ZEBRAY", "table": hash}).to_string();
+        assert_eq!(adapter.decode(&json).unwrap(), r#"{"result":"ABCDEZ"}"#);
+
+        //指定しなければ、どの文字も自分自身には対応しない
+        let json = r#"{"passphrase": "zebras", "script": "latin"}"#;
+        let result = adapter.generate_table(json).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let mapping = body["result"]["mapping"].as_object().unwrap();
+        assert!(mapping.iter().all(|(plain, cipher)| cipher != plain));
+    }
+
+    #[test]
+    fn test_generate_names() {
+        let adapter = create_adapter();
+        let json = r#"{"count": 3, "syllables": 2, "neutral": true, "seed": 42}"#;
+        let result = adapter.generate_names(json).unwrap();
+        assert_eq!(result, adapter.generate_names(json).unwrap());
+
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(body["seed"], 42);
+        assert_eq!(body["result"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_generate_names_invalid_syllables() {
+        let adapter = create_adapter();
+        let json = r#"{"syllables": 9}"#;
+        assert_eq!(
+            adapter.generate_names(json).err().unwrap().to_string(),
+            "Syllable count must be between 1 and 4"
+        );
+    }
+
+    #[test]
+    fn test_list_ciphers() {
+        let adapter = create_adapter();
+        let result = adapter.list_ciphers().unwrap();
+        assert_eq!(
+            result,
+            r#"{"result":[{"name":"albhed","kind":"builtin","description":"Al Bhed from Final Fantasy X","scripts":["latin","kana"],"size":106}]}"#
+        );
+
+        //合言葉から作った表は一覧に出さず、ハッシュを指定したときだけ引ける
+        let json = r#"{"passphrase": "zebras", "script": "latin"}"#;
+        let generated: serde_json::Value =
+            serde_json::from_str(&adapter.generate_table(json).unwrap()).unwrap();
+        assert_eq!(adapter.list_ciphers().unwrap(), result);
+        let hash = generated["result"]["hash"].as_str().unwrap();
+        let body: serde_json::Value =
+            serde_json::from_str(&adapter.cipher_alphabet(hash).unwrap()).unwrap();
+        assert_eq!(body["result"]["scripts"]["latin"]["forward"]["A"], "Z");
+    }
+
+    #[test]
+    fn test_cipher_alphabet() {
+        let adapter = create_adapter();
+        let result = adapter.cipher_alphabet("albhed").unwrap();
+        let body: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(body["result"]["scripts"]["latin"]["forward"]["A"], "Y");
+        assert_eq!(body["result"]["scripts"]["latin"]["reverse"]["Y"], "A");
+        assert_eq!(body["result"]["scripts"]["kana"]["forward"]["あ"], "ワ");
+
+        assert_eq!(
+            adapter
+                .cipher_alphabet("unknown")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Unknown table: unknown"
+        );
+    }
+
+    #[test]
+    fn test_encode_with_named_table() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "HI", "table": "albhed"}"#;
+        assert_eq!(adapter.encode(json).unwrap(), r#"{"result":"RE"}"#);
+    }
+
+    #[test]
+    fn test_generate_table_invalid_script() {
+        let adapter = create_adapter();
+        let json = r#"{"passphrase": "zebras", "script": "runic"}"#;
+        assert!(adapter.generate_table(json).is_err());
+    }
+
+    #[test]
+    fn test_encode_with_pipeline() {
+        let adapter = create_adapter();
+        let json =
+            r#"{"text": " hello", "pipeline": {"profile": "normalize", "pre": ["uppercase"]}}"#;
+        let body: serde_json::Value = serde_json::from_str(&adapter.encode(json).unwrap()).unwrap();
+
+        assert_eq!(body["result"], "RAMMU");
+        assert_eq!(body["stages"][0]["stage"], "trim");
+        assert_eq!(body["stages"][0]["phase"], "pre");
+        assert_eq!(
+            body["stages"][4]["changes"][0],
+            serde_json::json!({"start": 0, "end": 5, "before": "hello", "after": "HELLO"})
+        );
+    }
+
+    #[test]
+    fn test_decode_with_unknown_pipeline_stage() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "RAMMU", "pipeline": {"post": ["rot13"]}}"#;
+        let result = adapter.decode(json);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unknown pipeline stage: rot13"
+        );
+    }
+
+    #[test]
+    fn test_translate() {
+        let adapter = create_adapter();
+        let json =
+            r#"{"text": "Hello", "direction": "encode", "case": "preserve", "scripts": ["latin"]}"#;
+        let body: serde_json::Value =
+            serde_json::from_str(&adapter.translate(json).unwrap()).unwrap();
+
+        assert_eq!(body["result"], "Rammu");
+        assert_eq!(body["direction"], "encode");
+        assert_eq!(
+            body["metadata"],
+            serde_json::json!({"table": "albhed", "kind": "builtin", "scripts": ["latin"]})
+        );
+        assert!(body.get("warnings").is_none());
+    }
+
+    #[test]
+    fn test_translate_warnings() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "RAMMU", "direction": "decode", "pronounce": true}"#;
+        let body: serde_json::Value =
+            serde_json::from_str(&adapter.translate(json).unwrap()).unwrap();
+
+        assert_eq!(body["result"], "HELLO");
+        assert_eq!(
+            body["warnings"],
+            serde_json::json!([{"type": "ignored_option", "option": "pronunciation"}])
+        );
+    }
+
+    #[test]
+    fn test_translate_invalid_direction() {
+        let adapter = create_adapter();
+        let json = r#"{"text": "RAMMU", "direction": "sideways"}"#;
+
+        assert_eq!(
+            adapter.translate(json).unwrap_err().to_string(),
+            "Invalid JSON format"
+        );
+    }
 }
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use crate::domain::error::DomainError;
+use crate::usecase::error::UsecaseError;
+
+#[derive(Debug, Error)]
+pub enum AdapterError {
+    #[error("Invalid JSON format")]
+    InvalidJson(#[source] serde_json::Error),
+    #[error("Failed to serialize response")]
+    Serialization(#[source] serde_json::Error),
+    #[error(transparent)]
+    Usecase(#[from] UsecaseError),
+}
+
+impl AdapterError {
+    //APIの利用者に返す、変わらない識別子
+    pub fn code(&self) -> &'static str {
+        match self {
+            AdapterError::InvalidJson(_) => "invalid_json",
+            AdapterError::Serialization(_) => "serialization_failed",
+            AdapterError::Usecase(error) => error.code(),
+        }
+    }
+}
+
+impl From<DomainError> for AdapterError {
+    fn from(error: DomainError) -> Self {
+        AdapterError::Usecase(error.into())
+    }
+}
@@ -1,4 +1,4 @@
-pub mod domain;
+pub use albhed_usecase::{domain, usecase};
+
 pub mod infrastracture;
 pub mod interface;
-pub mod usecase;
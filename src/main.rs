@@ -1,13 +1,82 @@
+use std::sync::Arc;
+
 use albhed_translator_service::{
-    infrastracture::web,
+    domain::{
+        pipeline::Pipeline,
+        table_registry::{TableRegistry, DEFAULT_KEYED_LIMIT},
+    },
+    infrastracture::{
+        wasm_plugin::{load_plugins, PluginLimits},
+        web,
+    },
     interface::adapter::JsonAlBhedTranslatorAdapter,
-    usecase::{decode_usecase::DecodeInteractor, encode_usecase::EncodeInteractor},
+    usecase::{
+        cipher_usecase::CipherInteractor,
+        decode_usecase::DecodeInteractor,
+        encode_usecase::EncodeInteractor,
+        error::UsecaseError,
+        input_lint::LintMode,
+        name_usecase::NameInteractor,
+        spellcheck_usecase::SpellcheckInteractor,
+        table_usecase::TableInteractor,
+        translate_usecase::{TranslateInteractor, DEFAULT_PARALLEL_THRESHOLD},
+    },
 };
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let encode_port = EncodeInteractor::new();
-    let decode_port = DecodeInteractor::new();
-    let adapter = JsonAlBhedTranslatorAdapter::new(Box::new(encode_port), Box::new(decode_port));
+    let lint_mode: LintMode = std::env::var("LINT_MODE")
+        .unwrap_or_else(|_| "report".to_string())
+        .parse()
+        .map_err(|error: UsecaseError| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, error)
+        })?;
+    let pipeline = Pipeline::profile(
+        &std::env::var("PIPELINE_PROFILE").unwrap_or_else(|_| "plain".to_string()),
+    )
+    .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+
+    let parallel_threshold = std::env::var("PARALLEL_THRESHOLD")
+        .ok()
+        .and_then(|bytes| bytes.parse().ok())
+        .unwrap_or(DEFAULT_PARALLEL_THRESHOLD);
+
+    let keyed_limit = std::env::var("KEYED_TABLE_LIMIT")
+        .ok()
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(DEFAULT_KEYED_LIMIT);
+
+    let registry = Arc::new(TableRegistry::new().with_keyed_limit(keyed_limit));
+    if let Ok(directory) = std::env::var("PLUGIN_DIR") {
+        load_plugins(directory.as_ref(), &registry, PluginLimits::default())
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+    }
+    let encode_port = EncodeInteractor::with_registry(registry.clone())
+        .with_lint_mode(lint_mode)
+        .with_pipeline(pipeline.clone())
+        .with_parallel_threshold(parallel_threshold);
+    let decode_port = DecodeInteractor::with_registry(registry.clone())
+        .with_lint_mode(lint_mode)
+        .with_pipeline(pipeline.clone())
+        .with_parallel_threshold(parallel_threshold);
+    let translate_port = TranslateInteractor::with_registry(registry.clone())
+        .with_lint_mode(lint_mode)
+        .with_pipeline(pipeline)
+        .with_parallel_threshold(parallel_threshold);
+    let spellcheck_port = SpellcheckInteractor::new();
+    let table_secret = std::env::var("KEYED_TABLE_SECRET").unwrap_or_default();
+    let table_port =
+        TableInteractor::with_registry(registry.clone()).with_secret(table_secret.as_bytes());
+    let cipher_port = CipherInteractor::with_registry(registry);
+    let name_port = NameInteractor::new();
+    let adapter = JsonAlBhedTranslatorAdapter::new(
+        Box::new(encode_port),
+        Box::new(decode_port),
+        Box::new(spellcheck_port),
+        Box::new(table_port),
+        Box::new(name_port),
+        Box::new(cipher_port),
+        Box::new(translate_port),
+    );
     web::start_server(adapter).await
 }
@@ -1,2 +0,0 @@
-pub mod decode_usecase;
-pub mod encode_usecase;